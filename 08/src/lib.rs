@@ -0,0 +1,153 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_core::{Answer, ParseMode, Result, Solution};
+use aoc_grid::{Grid, ParseGridError, Point};
+
+/// Path of the puzzle input shipped alongside this crate.
+pub const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day_8.in");
+
+/// Antenna positions grouped by frequency character.
+pub type AntennaMap = HashMap<char, Vec<Point>>;
+
+// Compute Greatest Common Divisor (GCD)
+pub fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Signed (dx, dy) offset leading from `from` to `to`.
+fn delta(from: Point, to: Point) -> (isize, isize) {
+    (
+        to.x as isize - from.x as isize,
+        to.y as isize - from.y as isize,
+    )
+}
+
+// Part One Logic:
+// For each pair of antennas with the same frequency, generate the two antinodes at 2:1 ratio.
+pub fn compute_part_one_antinodes(grid: &Grid<char>, antennas_by_freq: &AntennaMap) -> usize {
+    let mut antinodes = HashSet::new();
+    for positions in antennas_by_freq.values() {
+        for i in 0..positions.len() {
+            for j in i + 1..positions.len() {
+                let (p1, p2) = (positions[i], positions[j]);
+                let (dx, dy) = delta(p1, p2);
+
+                // The antinodes lie one step beyond each antenna, away from the other one;
+                // insert those that fall inside the grid
+                antinodes.extend(grid.step(p1, -dx, -dy));
+                antinodes.extend(grid.step(p2, dx, dy));
+            }
+        }
+    }
+    antinodes.len()
+}
+
+// Part Two Logic:
+// For each pair of antennas with the same frequency, find all integer lattice points on the line
+// through them (both directions) and mark them as antinodes.
+pub fn compute_part_two_antinodes(grid: &Grid<char>, antennas_by_freq: &AntennaMap) -> usize {
+    let mut antinodes = HashSet::new();
+
+    for positions in antennas_by_freq.values() {
+        // Skip frequencies with only one antenna (they don't create lines by themselves)
+        if positions.len() < 2 {
+            continue;
+        }
+
+        for i in 0..positions.len() {
+            for j in i + 1..positions.len() {
+                let (dx, dy) = delta(positions[i], positions[j]);
+                let g = gcd(dx, dy);
+                let step = (dx / g, dy / g);
+
+                // Extend forward, then backward, until leaving the grid
+                for (step_x, step_y) in [step, (-step.0, -step.1)] {
+                    let mut current = Some(positions[i]);
+                    while let Some(p) = current {
+                        antinodes.insert(p);
+                        current = grid.step(p, step_x, step_y);
+                    }
+                }
+            }
+        }
+    }
+
+    antinodes.len()
+}
+
+/// Parses the antenna grid and collects the antenna positions by frequency.
+pub fn parse_antennas(
+    input: &str,
+) -> std::result::Result<(Grid<char>, AntennaMap), ParseGridError> {
+    let grid = Grid::parse(input)?;
+
+    // Collect antennas by frequency
+    let mut antennas_by_freq: AntennaMap = HashMap::new();
+    for (p, &ch) in grid.iter() {
+        if ch != '.' {
+            antennas_by_freq.entry(ch).or_default().push(p);
+        }
+    }
+
+    Ok((grid, antennas_by_freq))
+}
+
+/// Day 8: Resonant Collinearity.
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = (Grid<char>, AntennaMap);
+
+    fn parse(input: &str, _mode: ParseMode) -> Result<Self::Input> {
+        Ok(parse_antennas(input)?)
+    }
+
+    /// Number of antinode locations at the 2:1 points.
+    fn part1((grid, antennas_by_freq): &Self::Input) -> Result<Answer> {
+        Ok(compute_part_one_antinodes(grid, antennas_by_freq).into())
+    }
+
+    /// Number of antinode locations anywhere on the antenna lines.
+    fn part2((grid, antennas_by_freq): &Self::Input) -> Result<Answer> {
+        Ok(compute_part_two_antinodes(grid, antennas_by_freq).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.in");
+    const EXAMPLE_T: &str = include_str!("../fixtures/example_t.in");
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(6, 4), 2);
+        assert_eq!(gcd(-6, 4), 2);
+        assert_eq!(gcd(0, 5), 5);
+    }
+
+    #[test]
+    fn test_example_part_one() {
+        let (grid, antennas) = parse_antennas(EXAMPLE).unwrap();
+        assert_eq!(antennas[&'0'].len(), 4);
+        assert_eq!(antennas[&'A'].len(), 3);
+        assert_eq!(compute_part_one_antinodes(&grid, &antennas), 14);
+    }
+
+    #[test]
+    fn test_example_part_two() {
+        let (grid, antennas) = parse_antennas(EXAMPLE).unwrap();
+        assert_eq!(compute_part_two_antinodes(&grid, &antennas), 34);
+
+        // Only the T antennas: the antennas themselves count as antinodes
+        let (grid, antennas) = parse_antennas(EXAMPLE_T).unwrap();
+        assert_eq!(compute_part_two_antinodes(&grid, &antennas), 9);
+    }
+}
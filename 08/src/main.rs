@@ -0,0 +1,49 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc_core::cli::{self, Args};
+use aoc_core::{Format, ParseMode, Result, Solution};
+use day08::Day08;
+
+/// The bundled puzzle input, used when no input is given.
+#[cfg(feature = "embedded-input")]
+const EMBEDDED_INPUT: Option<&str> = Some(include_str!("../day_8.in"));
+#[cfg(not(feature = "embedded-input"))]
+const EMBEDDED_INPUT: Option<&str> = None;
+
+/// Reads the grid from the file given as the only argument, or from stdin for '-'.
+/// Without an argument the embedded input is used if compiled in, stdin otherwise.
+fn read_grid(path: Option<&str>) -> Result<String> {
+    match (path, EMBEDDED_INPUT) {
+        (Some("-"), _) | (None, None) => aoc_core::read_input(None),
+        (Some(path), _) => aoc_core::read_input(Some(Path::new(path))),
+        (None, Some(embedded)) => Ok(embedded.to_string()),
+    }
+}
+
+fn run(mut args: Args) -> Result<()> {
+    let format: Format = args.parsed("--format")?.unwrap_or_default();
+    let path = args.positional();
+    args.finish()?;
+
+    let input = read_grid(path.as_deref())?;
+    if format == Format::Json {
+        return aoc_core::print_json::<Day08>(&input, ParseMode::Strict);
+    }
+
+    // Collect antennas by frequency
+    let antennas = Day08::parse(&input, ParseMode::Strict)?;
+
+    // Compute results for Part One and Part Two
+    let part_one_result = Day08::part1(&antennas)?;
+    let part_two_result = Day08::part2(&antennas)?;
+
+    println!("day 8 part one: {}", part_one_result);
+    println!("day 8 part two: {}", part_two_result);
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    cli::report(run(Args::from_env()))
+}
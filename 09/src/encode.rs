@@ -0,0 +1,214 @@
+use aoc_core::{Error, Result};
+
+use crate::{parse_disk_map, Block, Disk};
+
+/// A disk written back in the puzzle's dense format.
+///
+/// `parse_disk_map` numbers files 0, 1, 2... in map order, which a compacted disk
+/// no longer follows, so the real id of each file digit is kept in `ids`. Digits
+/// that only exist to keep files and free space alternating have no id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedDisk {
+    pub disk_map: String,
+    pub ids: Vec<Option<usize>>,
+}
+
+impl EncodedDisk {
+    /// Whether parsing `disk_map` alone already gives every file its id.
+    pub fn has_sequential_ids(&self) -> bool {
+        self.ids
+            .iter()
+            .enumerate()
+            .all(|(position, id)| id.is_none_or(|id| id == position))
+    }
+
+    /// The id table as space-separated ids, with '-' for digits without one.
+    pub fn ids_to_string(&self) -> String {
+        let ids: Vec<String> = self
+            .ids
+            .iter()
+            .map(|id| id.map_or("-".to_string(), |id| id.to_string()))
+            .collect();
+        ids.join(" ")
+    }
+
+    /// Parses the disk map and gives each file its real id.
+    pub fn decode(&self) -> Result<Disk> {
+        relabel(&parse_disk_map(&self.disk_map), &self.ids)
+    }
+}
+
+/// Encodes any disk layout as a disk map.
+///
+/// Runs longer than 9 blocks are split into several digits, separated by a '0'
+/// of the other kind so files and free space keep alternating. The same '0' sits
+/// between two neighbouring files and in front of a disk that starts with free space.
+pub fn encode_disk(disk: &Disk) -> EncodedDisk {
+    let mut encoder = Encoder {
+        encoded: EncodedDisk {
+            disk_map: String::new(),
+            ids: Vec::new(),
+        },
+        expect_file: true,
+    };
+    for (block, length) in runs(disk) {
+        for chunk in chunks(length) {
+            match block {
+                Some(id) => encoder.push_file(Some(id), chunk),
+                None => encoder.push_free(chunk),
+            }
+        }
+    }
+    encoder.encoded
+}
+
+/// Encodes a block-per-entry layout as a disk map.
+pub fn encode_blocks(blocks: &[Block]) -> EncodedDisk {
+    encode_disk(&Disk::from_blocks(blocks))
+}
+
+/// Parses an id table written by [`EncodedDisk::ids_to_string`].
+pub fn parse_ids(input: &str) -> Result<Vec<Option<usize>>> {
+    let mut ids = Vec::new();
+    for (line_num, line) in input.lines().enumerate() {
+        let mut column = 1;
+        for token in line.split(' ') {
+            if !token.is_empty() {
+                let id = match token {
+                    "-" => None,
+                    _ => Some(token.parse().map_err(|_| {
+                        Error::parse(line_num + 1, column, format!("invalid id '{}'", token))
+                    })?),
+                };
+                ids.push(id);
+            }
+            column += token.len() + 1;
+        }
+    }
+    Ok(ids)
+}
+
+/// Gives each file of a freshly parsed disk its real id: the file numbered `n`
+/// by `parse_disk_map` becomes `ids[n]`.
+pub fn relabel(disk: &Disk, ids: &[Option<usize>]) -> Result<Disk> {
+    let mut relabelled = Disk::default();
+    for (block, length) in runs(disk) {
+        let block = match block {
+            Some(position) => Some(ids.get(position).copied().flatten().ok_or_else(|| {
+                Error::Mismatch(format!("the id table has no id for file {}", position))
+            })?),
+            None => None,
+        };
+        relabelled.push(block, length);
+    }
+    Ok(relabelled)
+}
+
+struct Encoder {
+    encoded: EncodedDisk,
+    /// Whether the next digit of the map describes a file.
+    expect_file: bool,
+}
+
+impl Encoder {
+    fn push_file(&mut self, id: Option<usize>, length: usize) {
+        if !self.expect_file {
+            self.push_digit(0);
+        }
+        self.push_digit(length);
+        self.encoded.ids.push(id);
+    }
+
+    fn push_free(&mut self, length: usize) {
+        if self.expect_file {
+            self.push_file(None, 0);
+        }
+        self.push_digit(length);
+    }
+
+    fn push_digit(&mut self, digit: usize) {
+        self.encoded.disk_map.push(char::from(b'0' + digit as u8));
+        self.expect_file = !self.expect_file;
+    }
+}
+
+/// The disk as runs of file or free blocks, in order.
+fn runs(disk: &Disk) -> Vec<(Block, usize)> {
+    let mut runs: Vec<(usize, Block, usize)> = disk
+        .extents()
+        .map(|file| (file.start, Some(file.id), file.length))
+        .chain(
+            disk.free_spans()
+                .map(|(start, length)| (start, None, length)),
+        )
+        .collect();
+    runs.sort_unstable_by_key(|&(start, _, _)| start);
+    runs.into_iter()
+        .map(|(_, block, length)| (block, length))
+        .collect()
+}
+
+/// Splits a run into digits of at most 9.
+fn chunks(length: usize) -> impl Iterator<Item = usize> {
+    (0..length.div_ceil(9)).map(move |i| (length - i * 9).min(9))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compact_part_two, STRATEGIES};
+
+    #[test]
+    fn test_encode_parsed_maps_round_trip() {
+        for disk_map in ["12345", "2333133121414131402", "9", "90909"] {
+            let encoded = encode_disk(&parse_disk_map(disk_map));
+            assert_eq!(encoded.disk_map, disk_map);
+            assert!(encoded.has_sequential_ids());
+        }
+    }
+
+    #[test]
+    fn test_encode_compacted_disks() {
+        let disk = parse_disk_map("2333133121414131402");
+        for strategy in STRATEGIES {
+            let mut compacted = disk.clone();
+            strategy.compact(&mut compacted);
+            let encoded = encode_disk(&compacted);
+            assert_eq!(encoded.decode().unwrap(), compacted, "{}", strategy.name());
+        }
+
+        // 00992111777.44.333....5555.6666.....8888..
+        let mut compacted = disk.clone();
+        compact_part_two(&mut compacted);
+        let encoded = encode_disk(&compacted);
+        assert_eq!(encoded.disk_map, "20201030312134414542");
+        assert_eq!(encoded.ids_to_string(), "0 9 2 1 7 4 3 5 6 8");
+        assert!(!encoded.has_sequential_ids());
+    }
+
+    #[test]
+    fn test_encode_long_runs_and_leading_free_space() {
+        // 3 free blocks, 20 blocks of file 7, 12 free, 1 block of file 3
+        let mut blocks = vec![None; 3];
+        blocks.extend([Some(7); 20]);
+        blocks.extend([None; 12]);
+        blocks.push(Some(3));
+
+        let encoded = encode_blocks(&blocks);
+        assert_eq!(encoded.disk_map, "03909029031");
+        assert_eq!(encoded.ids_to_string(), "- 7 7 7 - 3");
+        assert_eq!(encoded.decode().unwrap().to_blocks(), blocks);
+
+        let ids = parse_ids(&encoded.ids_to_string()).unwrap();
+        assert_eq!(ids, encoded.ids);
+    }
+
+    #[test]
+    fn test_relabel_errors() {
+        let err = parse_ids("0 1 x").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 5: invalid id 'x'");
+
+        let err = relabel(&parse_disk_map("111"), &[Some(4)]).unwrap_err();
+        assert_eq!(err.to_string(), "the id table has no id for file 1");
+    }
+}
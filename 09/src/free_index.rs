@@ -0,0 +1,105 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::Disk;
+
+/// Spans of this length or longer share the last bucket.
+const MAX_BUCKET: usize = 9;
+
+/// Finds the leftmost free span that fits a file without scanning the disk.
+///
+/// Free span starts are kept in one min-heap per span length, with every span of
+/// `MAX_BUCKET` blocks or more in the last heap. The index is not told when the
+/// disk changes: entries are checked against the disk when they reach the top of
+/// a heap and dropped if the span there no longer has that length. Spans created
+/// by a move must be added with [`FreeIndex::insert`].
+#[derive(Debug, Clone)]
+pub struct FreeIndex {
+    heaps: Vec<BinaryHeap<Reverse<usize>>>,
+}
+
+impl FreeIndex {
+    /// Indexes every free span of `disk`.
+    pub fn new(disk: &Disk) -> Self {
+        let mut index = FreeIndex {
+            heaps: vec![BinaryHeap::new(); MAX_BUCKET + 1],
+        };
+        for (start, length) in disk.free_spans() {
+            index.insert(start, length);
+        }
+        index
+    }
+
+    /// Adds the free span `start..start + length`.
+    pub fn insert(&mut self, start: usize, length: usize) {
+        if length > 0 {
+            self.heaps[bucket(length)].push(Reverse(start));
+        }
+    }
+
+    /// Returns the start of the leftmost free span of `disk` holding at least
+    /// `length` blocks that ends at or before `before_pos`.
+    pub fn find(&mut self, disk: &Disk, length: usize, before_pos: usize) -> Option<usize> {
+        if length == 0 {
+            return None;
+        }
+        if length > MAX_BUCKET {
+            // Long files are rare; the last bucket does not order spans by length
+            return crate::find_leftmost_free_span(disk, length, before_pos);
+        }
+
+        let mut best = None;
+        for bucket_length in length..=MAX_BUCKET {
+            let Some(start) = self.peek(disk, bucket_length) else {
+                continue;
+            };
+            if best.is_none_or(|best| start < best) {
+                best = Some(start);
+            }
+        }
+        // Any other fitting span starts further right, so it cannot end earlier
+        best.filter(|&start| start + length <= before_pos)
+    }
+
+    /// The leftmost span still in the bucket for `bucket_length`, dropping stale entries.
+    fn peek(&mut self, disk: &Disk, bucket_length: usize) -> Option<usize> {
+        let heap = &mut self.heaps[bucket_length];
+        while let Some(&Reverse(start)) = heap.peek() {
+            match disk.free.get(&start) {
+                Some(&length) if bucket(length) == bucket_length => return Some(start),
+                _ => {
+                    heap.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+fn bucket(length: usize) -> usize {
+    length.min(MAX_BUCKET)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{move_file, parse_disk_map};
+
+    #[test]
+    fn test_free_index_skips_stale_spans() {
+        // 0..111....22222
+        let mut disk = parse_disk_map("12345");
+        let mut index = FreeIndex::new(&disk);
+        assert_eq!(index.find(&disk, 2, 10), Some(1));
+        assert_eq!(index.find(&disk, 3, 10), Some(6));
+        assert_eq!(index.find(&disk, 3, 8), None);
+
+        // Moving behind the index's back leaves both of its entries stale:
+        // 1..3 is filled and 6..10 has merged into 6..12
+        move_file(&mut disk, 2, 10, 1, 2);
+        assert_eq!(index.find(&disk, 2, 20), None);
+
+        index.insert(6, 6);
+        assert_eq!(index.find(&disk, 6, 20), Some(6));
+    }
+}
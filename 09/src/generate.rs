@@ -0,0 +1,276 @@
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// How the lengths of files or free spans are drawn, each a single digit.
+///
+/// Built with [`Lengths::fixed`], [`Lengths::uniform`] or [`Lengths::weighted`],
+/// or parsed, all of which reject settings that could draw anything but a digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lengths(Kind);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Fixed(u8),
+    Uniform(u8, u8),
+    Weighted([u32; 10]),
+}
+
+impl Lengths {
+    /// Always the same length.
+    pub fn fixed(length: u8) -> std::result::Result<Self, String> {
+        Ok(Lengths(Kind::Fixed(check_digit(length)?)))
+    }
+
+    /// Any length from `min` to `max`, inclusive, equally likely.
+    pub fn uniform(min: u8, max: u8) -> std::result::Result<Self, String> {
+        let (min, max) = (check_digit(min)?, check_digit(max)?);
+        if min > max {
+            return Err(format!("empty range {}-{}", min, max));
+        }
+        Ok(Lengths(Kind::Uniform(min, max)))
+    }
+
+    /// Length `n` with probability `weights[n] / sum(weights)`. The weights
+    /// must not all be 0.
+    pub fn weighted(weights: [u32; 10]) -> std::result::Result<Self, String> {
+        if weights.iter().all(|&w| w == 0) {
+            return Err("the weights must not all be 0".to_string());
+        }
+        Ok(Lengths(Kind::Weighted(weights)))
+    }
+
+    fn sample(&self, rng: &mut Rng) -> u8 {
+        match self.0 {
+            Kind::Fixed(length) => length,
+            Kind::Uniform(min, max) => min + (rng.below(u64::from(max - min) + 1)) as u8,
+            Kind::Weighted(weights) => {
+                let total: u64 = weights.iter().map(|&w| u64::from(w)).sum();
+                let mut pick = rng.below(total);
+                for (length, &weight) in weights.iter().enumerate() {
+                    if pick < u64::from(weight) {
+                        return length as u8;
+                    }
+                    pick -= u64::from(weight);
+                }
+                unreachable!("weights sum to {}", total)
+            }
+        }
+    }
+}
+
+fn check_digit(length: u8) -> std::result::Result<u8, String> {
+    if length <= 9 {
+        Ok(length)
+    } else {
+        Err(format!("'{}' is not a length from 0 to 9", length))
+    }
+}
+
+/// Parses `5` as fixed, `1-9` as uniform, or ten comma-separated weights for
+/// the lengths 0 to 9 as weighted.
+impl FromStr for Lengths {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let digit = |s: &str| -> std::result::Result<u8, String> {
+            s.trim()
+                .parse()
+                .map_err(|_| format!("'{}' is not a length from 0 to 9", s.trim()))
+        };
+
+        if s.contains(',') {
+            let weights: Vec<u32> = s
+                .split(',')
+                .map(|w| {
+                    w.trim()
+                        .parse()
+                        .map_err(|_| format!("invalid weight '{}'", w.trim()))
+                })
+                .collect::<std::result::Result<_, _>>()?;
+            let weights: [u32; 10] = weights
+                .try_into()
+                .map_err(|w: Vec<u32>| format!("expected 10 weights, found {}", w.len()))?;
+            Lengths::weighted(weights)
+        } else if let Some((min, max)) = s.split_once('-') {
+            Lengths::uniform(digit(min)?, digit(max)?)
+        } else {
+            Lengths::fixed(digit(s)?)
+        }
+    }
+}
+
+/// A seeded source of random disk maps. The same settings and seed always
+/// give the same map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    /// Number of files, counting zero-length ones.
+    pub files: usize,
+    pub file_lengths: Lengths,
+    /// Lengths of the free spans between files.
+    pub free_lengths: Lengths,
+    /// Chance from 0 to 1 that a file is empty, whatever `file_lengths` says.
+    pub zero_length_files: f64,
+    pub seed: u64,
+}
+
+impl Generator {
+    /// A generator shaped like the puzzle input: files of 1 to 9 blocks with
+    /// 0 to 9 free blocks between them.
+    pub fn new(files: usize, seed: u64) -> Self {
+        Generator {
+            files,
+            file_lengths: Lengths(Kind::Uniform(1, 9)),
+            free_lengths: Lengths(Kind::Uniform(0, 9)),
+            zero_length_files: 0.0,
+            seed,
+        }
+    }
+
+    /// The disk map as a string, file and free digits alternating and ending
+    /// with a file.
+    pub fn generate(&self) -> String {
+        self.digits().map(char::from).collect()
+    }
+
+    /// Writes the disk map without holding it in memory, followed by a newline.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut chunk = Vec::with_capacity(1 << 16);
+        for digit in self.digits() {
+            chunk.push(digit);
+            if chunk.len() == chunk.capacity() {
+                writer.write_all(&chunk)?;
+                chunk.clear();
+            }
+        }
+        chunk.push(b'\n');
+        writer.write_all(&chunk)?;
+        writer.flush()
+    }
+
+    /// The digits of the map as ASCII.
+    fn digits(&self) -> impl Iterator<Item = u8> + '_ {
+        let mut rng = Rng(self.seed);
+        (0..self.files).flat_map(move |file| {
+            let length = if rng.chance(self.zero_length_files) {
+                0
+            } else {
+                self.file_lengths.sample(&mut rng)
+            };
+            let free = (file + 1 < self.files).then(|| b'0' + self.free_lengths.sample(&mut rng));
+            std::iter::once(b'0' + length).chain(free)
+        })
+    }
+}
+
+/// SplitMix64, which is fast, seedable with any value and good enough here.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `bound`, which must not be 0.
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    /// True with the given probability.
+    fn chance(&mut self, probability: f64) -> bool {
+        // The top 53 bits as a fraction in [0, 1)
+        let fraction = (self.next() >> 11) as f64 / (1u64 << 53) as f64;
+        probability > 0.0 && fraction < probability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_disk_map;
+
+    #[test]
+    fn test_generator_is_seeded() {
+        let map = Generator::new(1_000, 1).generate();
+        assert_eq!(map.len(), 1_999);
+        assert_eq!(map, Generator::new(1_000, 1).generate());
+        assert_ne!(map, Generator::new(1_000, 2).generate());
+
+        // Files take 1 to 9 blocks by default
+        assert!(map
+            .bytes()
+            .step_by(2)
+            .all(|digit| (b'1'..=b'9').contains(&digit)));
+        assert_eq!(Generator::new(0, 1).generate(), "");
+        assert_eq!(Generator::new(1, 1).generate().len(), 1);
+
+        let mut written = Vec::new();
+        Generator::new(100_000, 3).write(&mut written).unwrap();
+        assert_eq!(
+            written,
+            format!("{}\n", Generator::new(100_000, 3).generate()).into_bytes()
+        );
+    }
+
+    #[test]
+    fn test_generator_lengths() {
+        let generator = Generator {
+            file_lengths: Lengths::fixed(3).unwrap(),
+            free_lengths: "0,0,1,0,0,0,0,0,0,1".parse().unwrap(),
+            zero_length_files: 0.5,
+            ..Generator::new(10_000, 4)
+        };
+        let map = generator.generate();
+        let files: Vec<u8> = map.bytes().step_by(2).collect();
+        let free: Vec<u8> = map.bytes().skip(1).step_by(2).collect();
+        assert!(files.iter().all(|&digit| digit == b'0' || digit == b'3'));
+        assert!(free.iter().all(|&digit| digit == b'2' || digit == b'9'));
+
+        // About half the files are empty, and they take no space
+        let empty = files.iter().filter(|&&digit| digit == b'0').count();
+        assert!((4_500..5_500).contains(&empty), "{} empty files", empty);
+        assert_eq!(parse_disk_map(&map).extents().count(), files.len() - empty);
+    }
+
+    #[test]
+    fn test_parse_lengths() {
+        assert_eq!("4".parse::<Lengths>().unwrap(), Lengths::fixed(4).unwrap());
+        assert_eq!(
+            "0-9".parse::<Lengths>().unwrap(),
+            Lengths::uniform(0, 9).unwrap()
+        );
+        assert_eq!(
+            "1,0,0,0,0,0,0,0,0,2".parse::<Lengths>().unwrap(),
+            Lengths::weighted([1, 0, 0, 0, 0, 0, 0, 0, 0, 2]).unwrap()
+        );
+        assert_eq!(
+            "10".parse::<Lengths>(),
+            Err("'10' is not a length from 0 to 9".to_string())
+        );
+        assert_eq!("5-2".parse::<Lengths>(), Err("empty range 5-2".to_string()));
+        assert_eq!(
+            "1,2".parse::<Lengths>(),
+            Err("expected 10 weights, found 2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lengths_reject_what_is_not_a_digit() {
+        assert_eq!(
+            Lengths::fixed(12),
+            Err("'12' is not a length from 0 to 9".to_string())
+        );
+        assert_eq!(Lengths::uniform(5, 2), Err("empty range 5-2".to_string()));
+        assert_eq!(
+            Lengths::uniform(0, 10),
+            Err("'10' is not a length from 0 to 9".to_string())
+        );
+        assert_eq!(
+            Lengths::weighted([0; 10]),
+            Err("the weights must not all be 0".to_string())
+        );
+    }
+}
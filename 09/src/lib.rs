@@ -0,0 +1,832 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs::File as StdFile;
+use std::io::BufReader;
+use std::path::Path;
+
+use aoc_core::{Answer, Error, ParseMode, Result, Solution};
+
+mod encode;
+mod free_index;
+mod generate;
+mod parallel;
+#[cfg(test)]
+mod properties;
+mod render;
+mod report;
+mod strategy;
+mod stream;
+mod trace;
+mod volume;
+
+pub use encode::{encode_blocks, encode_disk, parse_ids, relabel, EncodedDisk};
+pub use free_index::FreeIndex;
+pub use generate::{Generator, Lengths};
+pub use parallel::{part_one_checksum, part_one_layout, ParallelBlockByBlock};
+pub use render::{write_gif, write_png, HEATMAP_HEIGHT};
+pub use report::Report;
+pub use strategy::{
+    strategy, BestFit, BlockByBlock, CompactionStrategy, FirstFit, FirstFitSplitOnce, PackRight,
+    SizeOrder, SpreadEvenly, WorstFit, STRATEGIES,
+};
+pub use stream::{read_disk, DiskMapReader};
+pub use trace::{parse_trace, replay, verify_replay, write_trace, Move};
+pub use volume::{read_volume, Volume};
+
+/// Path of the puzzle input shipped alongside this crate.
+pub const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day_9.in");
+
+/// Represents a block on the disk.
+/// `Some(id)` indicates a file block with the given file ID.
+/// `None` indicates free space.
+pub type Block = Option<usize>;
+
+/// Represents a file with its ID, starting position, and length.
+/// On a [`Disk`] it is one extent: a run of consecutive blocks of the same file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskFile {
+    pub id: usize,
+    pub start: usize,
+    pub length: usize,
+}
+
+impl DiskFile {
+    /// Position just past the last block.
+    pub fn end(&self) -> usize {
+        self.start + self.length
+    }
+}
+
+/// A disk stored as extents instead of one entry per block.
+///
+/// File extents and free spans are both keyed by their start position. Free spans
+/// never touch each other and file extents of the same id never touch each other:
+/// both are merged as soon as they become adjacent, so the maps stay as small as
+/// the layout allows.
+#[derive(Debug, Clone, Default)]
+pub struct Disk {
+    files: BTreeMap<usize, DiskFile>,
+    free: BTreeMap<usize, usize>,
+    len: usize,
+    /// Sum of `position * id` over every file block, kept up to date as extents
+    /// are added and removed.
+    checksum: u128,
+    /// Number of file blocks, kept up to date like `checksum`.
+    used: usize,
+    /// Moves made so far, once recording has been turned on.
+    moves: Option<Vec<Move>>,
+}
+
+/// Disks are equal when their layouts are; recorded moves are ignored and the
+/// checksum follows from the layout.
+impl PartialEq for Disk {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.files == other.files && self.free == other.free
+    }
+}
+
+impl Eq for Disk {}
+
+impl Disk {
+    /// Builds a disk from a block-per-entry layout.
+    pub fn from_blocks(blocks: &[Block]) -> Self {
+        let mut disk = Disk::default();
+        for block in blocks {
+            disk.push(*block, 1);
+        }
+        disk
+    }
+
+    /// Expands the disk into one entry per block.
+    pub fn to_blocks(&self) -> Vec<Block> {
+        let mut blocks = vec![None; self.len];
+        for file in self.files.values() {
+            blocks[file.start..file.end()].fill(Some(file.id));
+        }
+        blocks
+    }
+
+    /// Total number of blocks, used or free.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// File extents in disk order.
+    pub fn extents(&self) -> impl DoubleEndedIterator<Item = &DiskFile> {
+        self.files.values()
+    }
+
+    /// The filesystem checksum of the disk as it is now, maintained on every
+    /// move instead of recomputed like [`calculate_checksum`].
+    pub fn checksum(&self) -> u128 {
+        self.checksum
+    }
+
+    /// Free spans in disk order, as `(start, length)`.
+    pub fn free_spans(&self) -> impl DoubleEndedIterator<Item = (usize, usize)> + '_ {
+        self.free.iter().map(|(&start, &length)| (start, length))
+    }
+
+    /// The free span containing block `pos`, as `(start, length)`.
+    pub fn free_span_at(&self, pos: usize) -> Option<(usize, usize)> {
+        self.free
+            .range(..=pos)
+            .next_back()
+            .filter(|&(&start, &length)| pos < start + length)
+            .map(|(&start, &length)| (start, length))
+    }
+
+    /// Starts recording every [`move_file`] call on this disk, dropping any earlier record.
+    pub fn record_moves(&mut self) {
+        self.moves = Some(Vec::new());
+    }
+
+    /// Stops recording and returns the moves made since [`Disk::record_moves`].
+    pub fn take_moves(&mut self) -> Vec<Move> {
+        self.moves.take().unwrap_or_default()
+    }
+
+    /// Checks that [`move_file`] can move `length` blocks of `file_id` from
+    /// `current_start` to `target_start`, describing the problem if it cannot.
+    pub fn check_move(
+        &self,
+        file_id: usize,
+        current_start: usize,
+        target_start: usize,
+        length: usize,
+    ) -> std::result::Result<(), String> {
+        if length == 0 {
+            return Err("a move must cover at least one block".to_string());
+        }
+        let end_of = |start: usize| {
+            start
+                .checked_add(length)
+                .ok_or_else(|| format!("blocks {}.. run past the largest position", start))
+        };
+        let current_end = end_of(current_start)?;
+        let owned = self
+            .files
+            .range(..=current_start)
+            .next_back()
+            .is_some_and(|(_, extent)| extent.id == file_id && current_end <= extent.end());
+        if !owned {
+            return Err(format!(
+                "blocks {}..{} do not belong to file {}",
+                current_start, current_end, file_id
+            ));
+        }
+
+        // The source is freed first, so the target may overlap it
+        let target_end = end_of(target_start)?;
+        let outside_source = [
+            (target_start, target_end.min(current_start)),
+            (target_start.max(current_end), target_end),
+        ];
+        for (start, end) in outside_source {
+            if start < end && !self.is_free(start, end) {
+                return Err(format!(
+                    "blocks {}..{} are not free",
+                    target_start, target_end
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether every block in `start..end` is free.
+    fn is_free(&self, start: usize, end: usize) -> bool {
+        self.free_span_at(start)
+            .is_some_and(|(span_start, span_length)| end <= span_start + span_length)
+    }
+
+    /// Appends `length` blocks of `block` at the end of the disk.
+    fn push(&mut self, block: Block, length: usize) {
+        let start = self.len;
+        self.len += length;
+        match block {
+            Some(id) => self.insert_extent(id, start, length),
+            None => self.insert_free(start, length),
+        }
+    }
+
+    /// Records `start..start + length` as free, merging it with free neighbours.
+    fn insert_free(&mut self, mut start: usize, mut length: usize) {
+        if length == 0 {
+            return;
+        }
+        if let Some((&before, &before_length)) = self.free.range(..start).next_back() {
+            if before + before_length == start {
+                self.free.remove(&before);
+                start = before;
+                length += before_length;
+            }
+        }
+        if let Some(after_length) = self.free.remove(&(start + length)) {
+            length += after_length;
+        }
+        self.free.insert(start, length);
+    }
+
+    /// Records `start..start + length` as blocks of file `id`, merging it with
+    /// neighbouring extents of the same file.
+    fn insert_extent(&mut self, id: usize, mut start: usize, mut length: usize) {
+        if length == 0 {
+            return;
+        }
+        self.checksum += extent_checksum(&DiskFile { id, start, length });
+        self.used += length;
+        let before = self
+            .files
+            .range(..start)
+            .next_back()
+            .map(|(_, extent)| extent);
+        if let Some(&DiskFile {
+            id: before_id,
+            start: before_start,
+            length: before_length,
+        }) = before
+        {
+            if before_id == id && before_start + before_length == start {
+                self.files.remove(&before_start);
+                start = before_start;
+                length += before_length;
+            }
+        }
+        let after = start + length;
+        if self.files.get(&after).is_some_and(|extent| extent.id == id) {
+            length += self.files.remove(&after).map_or(0, |extent| extent.length);
+        }
+        self.files.insert(start, DiskFile { id, start, length });
+    }
+
+    /// Removes `start..start + length` from the free span that contains it.
+    /// Panics if the range is not entirely free.
+    fn take_free(&mut self, start: usize, length: usize) {
+        let (&span_start, &span_length) = self
+            .free
+            .range(..=start)
+            .next_back()
+            .filter(|&(&span_start, &span_length)| start + length <= span_start + span_length)
+            .unwrap_or_else(|| panic!("blocks {}..{} are not free", start, start + length));
+        self.free.remove(&span_start);
+        if start > span_start {
+            self.free.insert(span_start, start - span_start);
+        }
+        let end = start + length;
+        if end < span_start + span_length {
+            self.free.insert(end, span_start + span_length - end);
+        }
+    }
+
+    /// Removes `start..start + length` from the extent of file `id` that contains it.
+    /// Panics if those blocks do not all belong to `id`.
+    fn take_extent(&mut self, id: usize, start: usize, length: usize) {
+        let extent = self
+            .files
+            .range(..=start)
+            .next_back()
+            .map(|(_, extent)| extent.clone())
+            .filter(|extent| extent.id == id && start + length <= extent.end())
+            .unwrap_or_else(|| {
+                panic!(
+                    "blocks {}..{} do not belong to file {}",
+                    start,
+                    start + length,
+                    id
+                )
+            });
+        self.files.remove(&extent.start);
+        self.checksum -= extent_checksum(&DiskFile { id, start, length });
+        self.used -= length;
+        if start > extent.start {
+            self.files.insert(
+                extent.start,
+                DiskFile {
+                    id,
+                    start: extent.start,
+                    length: start - extent.start,
+                },
+            );
+        }
+        let end = start + length;
+        if end < extent.end() {
+            self.files.insert(
+                end,
+                DiskFile {
+                    id,
+                    start: end,
+                    length: extent.end() - end,
+                },
+            );
+        }
+    }
+}
+
+/// Reads the disk map from the specified file without loading it into memory.
+/// Digits may be split over several lines or separated by whitespace.
+pub fn read_disk_map<P>(filename: P, mode: ParseMode) -> Result<Disk>
+where
+    P: AsRef<Path>,
+{
+    let filename = filename.as_ref();
+    let file = StdFile::open(filename).map_err(|source| Error::Io {
+        path: Some(filename.to_path_buf()),
+        source,
+    })?;
+    read_disk(BufReader::new(file), mode).map_err(|e| e.in_file(filename))
+}
+
+/// Parses the disk map string into a disk of extents.
+/// Alternates between file lengths and free space lengths, starting with a file.
+/// Characters other than digits are ignored; use [`read_disk`] to reject them.
+pub fn parse_disk_map(disk_map: &str) -> Disk {
+    let mut disk = Disk::default();
+
+    // File IDs start at 0
+    let mut file_id = 0;
+    // Flag to indicate whether the current digit represents a file length or free space length
+    let mut is_file = true;
+
+    for c in disk_map.chars() {
+        if let Some(length) = c.to_digit(10) {
+            let length = length as usize;
+            if is_file {
+                // Add an extent of `length` blocks with the current file ID
+                disk.push(Some(file_id), length);
+                file_id += 1;
+            } else {
+                // Add a free span of `length` blocks
+                disk.push(None, length);
+            }
+            // Toggle between file and free space
+            is_file = !is_file;
+        }
+    }
+
+    disk
+}
+
+/// Compacts the disk by moving individual file blocks to eliminate gaps.
+/// This corresponds to Part One of the challenge.
+/// Each step fills the first free span from the tail of the last extent, moving as
+/// many blocks at once as both can take, so the work grows with the number of extents.
+pub fn compact_part_one(disk: &mut Disk) {
+    loop {
+        let Some((free_start, free_length)) = disk.free_spans().next() else {
+            break;
+        };
+        let Some(last) = disk.extents().next_back() else {
+            break;
+        };
+        // Once the first free span lies after the last file block, compaction is complete
+        if free_start > last.start {
+            break;
+        }
+        let length = free_length.min(last.length);
+        let (id, from) = (last.id, last.end() - length);
+        move_file(disk, id, from, free_start, length);
+    }
+}
+
+/// Compacts the disk by moving entire files to the leftmost possible free space spans.
+/// This corresponds to Part Two of the challenge.
+pub fn compact_part_two(disk: &mut Disk) {
+    // Identify all files
+    let mut files = identify_files(disk);
+
+    // Sort files in decreasing order of file ID
+    files.sort_by_key(|file| Reverse(file.id));
+
+    move_files_first_fit(disk, files);
+}
+
+/// Moves each of `files` in turn to the leftmost free span that fits it
+/// entirely before its current position.
+fn move_files_first_fit(disk: &mut Disk, files: Vec<DiskFile>) {
+    let mut index = FreeIndex::new(disk);
+    for file in files {
+        // Find the leftmost free space span that can fit the file, entirely before the file's current position
+        if let Some(target_start) = index.find(disk, file.length, file.start) {
+            // Move the file to the target_start
+            move_file(disk, file.id, file.start, target_start, file.length);
+
+            // Index what is left of the target span and the span the file left behind
+            for pos in [target_start + file.length, file.start] {
+                if let Some((start, length)) = disk.free_span_at(pos) {
+                    index.insert(start, length);
+                }
+            }
+        }
+        // If no suitable span is found, the file does not move
+    }
+}
+
+/// Identifies all files on the disk, returning a vector of `DiskFile` structs.
+/// A file split into several extents is listed once per extent, in disk order.
+pub fn identify_files(disk: &Disk) -> Vec<DiskFile> {
+    disk.extents().cloned().collect()
+}
+
+/// Finds the leftmost free space span that can fit a file of given length,
+/// entirely before the specified position.
+/// Returns the starting index of the span if found.
+pub fn find_leftmost_free_span(disk: &Disk, length: usize, before_pos: usize) -> Option<usize> {
+    disk.free
+        .range(..before_pos)
+        .find(|&(&start, &span_length)| span_length >= length && start + length <= before_pos)
+        .map(|(&start, _)| start)
+}
+
+/// Finds the rightmost place a file of given length fits in a single free span,
+/// entirely after the specified position, packed against the end of that span.
+/// Returns the index the file would start at if found.
+pub fn find_rightmost_free_span(disk: &Disk, length: usize, after_pos: usize) -> Option<usize> {
+    disk.free
+        .range(after_pos..)
+        .rev()
+        .find(|&(_, &span_length)| span_length >= length)
+        .map(|(&start, &span_length)| start + span_length - length)
+}
+
+/// Moves `length` blocks of a file from `current_start` to `target_start`.
+/// The source blocks are freed first, so the target may overlap them.
+/// Panics if the source blocks do not all belong to `file_id` or the target is not free.
+pub fn move_file(
+    disk: &mut Disk,
+    file_id: usize,
+    current_start: usize,
+    target_start: usize,
+    length: usize,
+) {
+    // Set the old blocks to free
+    disk.take_extent(file_id, current_start, length);
+    disk.insert_free(current_start, length);
+
+    // Move the file blocks to the target_start
+    disk.take_free(target_start, length);
+    disk.insert_extent(file_id, target_start, length);
+
+    if let Some(moves) = &mut disk.moves {
+        moves.push(Move {
+            file_id,
+            from: current_start,
+            to: target_start,
+            length,
+            checksum: Some(disk.checksum),
+        });
+    }
+}
+
+/// Calculates the filesystem checksum based on the compacted disk.
+/// The checksum is the sum of (position * file_id) for all file blocks,
+/// computed per extent and in `u128` so huge disks cannot overflow it.
+pub fn calculate_checksum(disk: &Disk) -> u128 {
+    disk.extents().map(extent_checksum).sum()
+}
+
+/// Checksum of one extent: its id times the sum of the positions it covers,
+/// an arithmetic series from `start` to `start + length - 1`.
+pub fn extent_checksum(file: &DiskFile) -> u128 {
+    let (id, start, length) = (file.id as u128, file.start as u128, file.length as u128);
+    id * (length * start + length * length.saturating_sub(1) / 2)
+}
+
+/// Day 9: Disk Fragmenter.
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Disk;
+
+    fn parse(input: &str, mode: ParseMode) -> Result<Self::Input> {
+        read_disk(input.as_bytes(), mode)
+    }
+
+    /// Filesystem checksum after moving individual blocks.
+    fn part1(disk: &Self::Input) -> Result<Answer> {
+        Ok(part_one_checksum(disk).into())
+    }
+
+    /// Filesystem checksum after moving whole files.
+    fn part2(disk: &Self::Input) -> Result<Answer> {
+        let mut disk = disk.clone();
+        compact_part_two(&mut disk);
+        Ok(disk.checksum().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_disk_map_example1() {
+        let disk_map = "12345";
+        let disk = parse_disk_map(disk_map).to_blocks();
+        let expected = vec![
+            Some(0), // 1 block file ID 0
+            None,    // 2 blocks free
+            None,
+            Some(1), // 3 blocks file ID 1
+            Some(1),
+            Some(1),
+            None, // 4 blocks free
+            None,
+            None,
+            None,
+            Some(2), // 5 blocks file ID 2
+            Some(2),
+            Some(2),
+            Some(2),
+            Some(2),
+        ];
+        assert_eq!(disk, expected);
+    }
+
+    #[test]
+    fn test_parse_disk_map_example2() {
+        let disk_map = "2333133121414131402";
+        let disk = parse_disk_map(disk_map).to_blocks();
+        // Manually parsing:
+        // 2 (file 0, length 2)
+        // 3 (free, length 3)
+        // 3 (file 1, length 3)
+        // 3 (free, length 3)
+        // 1 (file 2, length 1)
+        // 3 (free, length 3)
+        // 3 (file 3, length 3)
+        // 1 (free, length 1)
+        // 2 (file 4, length 2)
+        // 1 (free, length 1)
+        // 4 (file 5, length 4)
+        // 1 (free, length 1)
+        // 4 (file 6, length 4)
+        // 1 (free, length 1)
+        // 3 (file 7, length 3)
+        // 1 (free, length 1)
+        // 4 (file 8, length 4)
+        // 0 (free, length 0) -> '0' means a zero-length span and adds no blocks.
+        // 2 (file 9, length 2)
+
+        // Constructing expected disk: 00...111...2...333.44.5555.6666.777.888899
+        let mut expected = Vec::new();
+        let runs: [(Block, usize); 19] = [
+            (Some(0), 2),
+            (None, 3),
+            (Some(1), 3),
+            (None, 3),
+            (Some(2), 1),
+            (None, 3),
+            (Some(3), 3),
+            (None, 1),
+            (Some(4), 2),
+            (None, 1),
+            (Some(5), 4),
+            (None, 1),
+            (Some(6), 4),
+            (None, 1),
+            (Some(7), 3),
+            (None, 1),
+            (Some(8), 4),
+            (None, 0),
+            (Some(9), 2),
+        ];
+        for (block, length) in runs {
+            expected.extend(std::iter::repeat_n(block, length));
+        }
+
+        assert_eq!(disk, expected);
+    }
+
+    #[test]
+    fn test_compact_part_one_example1() {
+        let disk_map = "12345";
+        let mut disk = parse_disk_map(disk_map);
+        assert_eq!(disk.to_string(), "0..111....22222");
+        compact_part_one(&mut disk);
+        assert_eq!(disk.to_string(), "022111222......");
+    }
+
+    #[test]
+    fn test_compact_part_one_example2() {
+        let disk_map = "2333133121414131402";
+        let mut disk = parse_disk_map(disk_map);
+        compact_part_one(&mut disk);
+        // Expected checksum is 1928
+        let checksum = calculate_checksum(&disk);
+        assert_eq!(checksum, 1928);
+    }
+
+    #[test]
+    fn test_compact_part_two_example1() {
+        let disk_map = "12345";
+        let mut disk = parse_disk_map(disk_map);
+        compact_part_two(&mut disk);
+        // No free span is large enough for any whole file, so nothing moves
+        assert_eq!(disk.to_string(), "0..111....22222");
+        // 1 * (3 + 4 + 5) + 2 * (10 + 11 + 12 + 13 + 14)
+        let checksum = calculate_checksum(&disk);
+        assert_eq!(checksum, 132);
+    }
+
+    #[test]
+    fn test_compact_part_two_example2() {
+        let disk_map = "2333133121414131402";
+        let mut disk = parse_disk_map(disk_map);
+        compact_part_two(&mut disk);
+        // Expected checksum is 2858
+        let checksum = calculate_checksum(&disk);
+        assert_eq!(checksum, 2858);
+    }
+
+    #[test]
+    fn test_calculate_checksum_empty() {
+        let disk: Vec<Block> = vec![];
+        let checksum = calculate_checksum(&Disk::from_blocks(&disk));
+        assert_eq!(checksum, 0);
+    }
+
+    #[test]
+    fn test_calculate_checksum_only_free() {
+        let disk: Vec<Block> = vec![None, None, None];
+        let checksum = calculate_checksum(&Disk::from_blocks(&disk));
+        assert_eq!(checksum, 0);
+    }
+
+    #[test]
+    fn test_calculate_checksum_single_file() {
+        let disk: Vec<Block> = vec![Some(0), Some(0), Some(0)];
+        let checksum = calculate_checksum(&Disk::from_blocks(&disk));
+        // Positions 0,1,2 with file ID 0: 0*0 + 1*0 + 2*0 = 0
+        assert_eq!(checksum, 0);
+    }
+
+    #[test]
+    fn test_example_fixture() {
+        // The fixture ends with a newline, like the real input
+        let input = include_str!("../fixtures/example.in");
+        let disk = Day09::parse(input, ParseMode::Strict).unwrap();
+        assert_eq!(Day09::part1(&disk).unwrap(), Answer::from(1928));
+        assert_eq!(Day09::part2(&disk).unwrap(), Answer::from(2858));
+    }
+
+    #[test]
+    fn test_disk_extents_and_free_spans() {
+        let disk = parse_disk_map("12345");
+        let extents: Vec<_> = disk.extents().map(|f| (f.id, f.start, f.length)).collect();
+        assert_eq!(extents, vec![(0, 0, 1), (1, 3, 3), (2, 10, 5)]);
+        assert_eq!(disk.free_spans().collect::<Vec<_>>(), vec![(1, 2), (6, 4)]);
+        assert_eq!(disk.len(), 15);
+        assert_eq!(Disk::from_blocks(&disk.to_blocks()), disk);
+    }
+
+    #[test]
+    fn test_move_file_merges_neighbours() {
+        // 0..111....22222
+        let mut disk = parse_disk_map("12345");
+
+        // Moving the middle block of file 1 leaves it split in two extents
+        move_file(&mut disk, 1, 4, 1, 1);
+        let extents: Vec<_> = disk.extents().map(|f| (f.id, f.start, f.length)).collect();
+        assert_eq!(
+            extents,
+            vec![(0, 0, 1), (1, 1, 1), (1, 3, 1), (1, 5, 1), (2, 10, 5)]
+        );
+        // The freed block is a span of its own, with file 1 on both sides of it
+        assert_eq!(
+            disk.free_spans().collect::<Vec<_>>(),
+            vec![(2, 1), (4, 1), (6, 4)]
+        );
+
+        // Filling the gap joins file 1 back into one extent
+        move_file(&mut disk, 1, 5, 2, 1);
+        let extents: Vec<_> = disk.extents().map(|f| (f.id, f.start, f.length)).collect();
+        assert_eq!(extents, vec![(0, 0, 1), (1, 1, 3), (2, 10, 5)]);
+        assert_eq!(disk.free_spans().collect::<Vec<_>>(), vec![(4, 6)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "are not free")]
+    fn test_move_file_onto_a_file_panics() {
+        let mut disk = parse_disk_map("12345");
+        move_file(&mut disk, 2, 10, 3, 1);
+    }
+
+    /// Part two as originally written: a linear scan of the free spans per file.
+    fn compact_part_two_by_scan(disk: &mut Disk) {
+        let mut files = identify_files(disk);
+        files.sort_by_key(|file| Reverse(file.id));
+        for file in files {
+            if let Some(target_start) = find_leftmost_free_span(disk, file.length, file.start) {
+                move_file(disk, file.id, file.start, target_start, file.length);
+            }
+        }
+    }
+
+    #[test]
+    fn test_free_index_matches_scan() {
+        for seed in 0..20 {
+            let disk = parse_disk_map(&Generator::new(1_000, seed).generate());
+            let mut indexed = disk.clone();
+            compact_part_two(&mut indexed);
+            let mut scanned = disk;
+            compact_part_two_by_scan(&mut scanned);
+            assert_eq!(indexed, scanned, "seed {}", seed);
+        }
+    }
+
+    /// Compacts a generated map of `files` files with Part Two, checks every
+    /// file is still whole and never moved right, and returns the checksum.
+    fn compact_generated_map(files: usize, seed: u64) -> u128 {
+        let disk = parse_disk_map(&Generator::new(files, seed).generate());
+        let before = identify_files(&disk);
+        let mut compacted = disk.clone();
+        compact_part_two(&mut compacted);
+        let after = identify_files(&compacted);
+
+        assert_eq!(before.len(), after.len());
+        let starts: HashMap<usize, usize> =
+            after.iter().map(|file| (file.id, file.start)).collect();
+        for file in &before {
+            assert!(starts[&file.id] <= file.start);
+        }
+        assert_eq!(compacted.checksum(), calculate_checksum(&compacted));
+        compacted.checksum()
+    }
+
+    #[test]
+    fn test_compact_part_two_large_map() {
+        // Fifty thousand files, a 100,000-digit map of about 475,000 blocks
+        let disk = parse_disk_map(&Generator::new(50_000, 9).generate());
+        let mut scanned = disk.clone();
+        compact_part_two_by_scan(&mut scanned);
+        assert_eq!(compact_generated_map(50_000, 9), scanned.checksum());
+    }
+
+    #[test]
+    #[ignore = "a million-digit map takes too long unoptimised; run with --release -- --ignored"]
+    fn test_compact_part_two_million_digit_map() {
+        // Half a million files and about 4.75 million blocks, the size the
+        // free-span index was written for
+        let disk = parse_disk_map(&Generator::new(500_000, 9).generate());
+        let mut scanned = disk.clone();
+        compact_part_two_by_scan(&mut scanned);
+        assert_eq!(compact_generated_map(500_000, 9), scanned.checksum());
+    }
+
+    #[test]
+    fn test_checksum_is_kept_up_to_date() {
+        let mut disk = parse_disk_map("2333133121414131402");
+        assert_eq!(disk.checksum(), calculate_checksum(&disk));
+        assert_eq!(Disk::default().checksum(), 0);
+
+        // 00...111...: file 0 one block right, then file 1 into the gap
+        move_file(&mut disk, 0, 0, 1, 2);
+        assert_eq!(disk.checksum(), calculate_checksum(&disk));
+        move_file(&mut disk, 1, 5, 0, 1);
+        assert_eq!(disk.checksum(), calculate_checksum(&disk));
+
+        compact_part_one(&mut disk);
+        assert_eq!(disk.checksum(), calculate_checksum(&disk));
+    }
+
+    #[test]
+    fn test_extent_checksum_closed_form() {
+        let file = DiskFile {
+            id: 7,
+            start: 4,
+            length: 3,
+        };
+        assert_eq!(extent_checksum(&file), 7 * (4 + 5 + 6));
+
+        // Far beyond what usize arithmetic can hold
+        let file = DiskFile {
+            id: usize::MAX,
+            start: usize::MAX / 2,
+            length: 2,
+        };
+        let start = (usize::MAX / 2) as u128;
+        assert_eq!(
+            extent_checksum(&file),
+            usize::MAX as u128 * (start + start + 1)
+        );
+    }
+
+    #[test]
+    fn test_read_disk_map_from_file() {
+        let disk = read_disk_map(INPUT_PATH, ParseMode::Strict).unwrap();
+        assert_eq!(Day09::part1(&disk).unwrap(), Answer::from(6283170117911u64));
+
+        // A directory opens but fails once read, and the error still names it
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let err = read_disk_map(dir, ParseMode::Strict).unwrap_err();
+        assert!(matches!(err, Error::Io { path: Some(ref path), .. } if path == Path::new(dir)));
+    }
+}
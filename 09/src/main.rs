@@ -0,0 +1,334 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use aoc_core::cli::{self, Args};
+use aoc_core::{Answer, Error, Format, ParseMode, Result, Solution};
+use day09::{
+    encode_disk, parse_ids, parse_trace, read_disk, read_volume, relabel, verify_replay, write_gif,
+    write_png, write_trace, BlockByBlock, CompactionStrategy, Day09, Disk, FirstFit, Generator,
+    Move, Report, Volume, HEATMAP_HEIGHT, INPUT_PATH, STRATEGIES,
+};
+
+/// Looks up the strategy given with `--strategy`, listing the known ones if it is unknown.
+fn parse_strategy(name: &str) -> Result<&'static dyn CompactionStrategy> {
+    day09::strategy(name).ok_or_else(|| {
+        let names: Vec<_> = STRATEGIES.iter().map(|strategy| strategy.name()).collect();
+        Error::Usage(format!(
+            "unknown strategy '{}' (expected one of: {})",
+            name,
+            names.join(", ")
+        ))
+    })
+}
+
+/// How a strategy run is reported, from the command-line options.
+struct Options {
+    format: Format,
+    /// Print move and fragmentation statistics.
+    report: bool,
+    /// Write every move as JSONL to this file.
+    trace: Option<String>,
+    /// Check that the moves in this file reproduce the compacted disk.
+    replay: Option<String>,
+    /// Write the compacted disk map to this file.
+    save: Option<String>,
+    /// Draw the compacted disk to this file, or animate the compaction for a GIF.
+    render: Option<String>,
+}
+
+/// Wraps an I/O error with the path it happened on.
+fn io_error(path: &str) -> impl FnOnce(io::Error) -> Error + '_ {
+    move |source| Error::Io {
+        path: Some(PathBuf::from(path)),
+        source,
+    }
+}
+
+/// Writes the disk as a disk map to `path`. If the map alone would number the
+/// files differently, the id table goes next to it in `<path>.ids`.
+fn save_disk(disk: &Disk, path: &str) -> Result<()> {
+    let encoded = encode_disk(disk);
+    fs::write(path, format!("{}\n", encoded.disk_map)).map_err(io_error(path))?;
+    if !encoded.has_sequential_ids() {
+        let ids_path = format!("{}.ids", path);
+        fs::write(&ids_path, format!("{}\n", encoded.ids_to_string()))
+            .map_err(io_error(&ids_path))?;
+        eprintln!(
+            "Wrote the id table to {}; pass it back with --ids",
+            ids_path
+        );
+    }
+    Ok(())
+}
+
+/// Draws the compaction of `initial` into `compacted`: an animation of every
+/// move for a `.gif`, a heatmap of the result for a `.png`, and the puzzle's
+/// notation for anything else, with '-' printing it.
+fn render_disk(initial: &Disk, compacted: &Disk, moves: &[Move], path: &str) -> Result<()> {
+    if path == "-" {
+        println!("{}", compacted);
+        return Ok(());
+    }
+    let mut writer = BufWriter::new(File::create(path).map_err(io_error(path))?);
+    if path.ends_with(".gif") {
+        write_gif(writer, initial, moves)
+    } else if path.ends_with(".png") {
+        write_png(writer, compacted, HEATMAP_HEIGHT)
+    } else {
+        writeln!(writer, "{}", compacted).and_then(|()| writer.flush())
+    }
+    .map_err(io_error(path))
+}
+
+/// Compacts the disk with one strategy and prints its checksum, followed by
+/// the statistics, trace and replay check the options ask for.
+fn run_strategy(disk: &Disk, strategy: &dyn CompactionStrategy, options: &Options) -> Result<()> {
+    let start = Instant::now();
+    let mut compacted = disk.clone();
+    let animate = options
+        .render
+        .as_deref()
+        .is_some_and(|path| path.ends_with(".gif"));
+    if options.trace.is_some() || options.report || animate {
+        compacted.record_moves();
+    }
+    strategy.compact(&mut compacted);
+    let moves = compacted.take_moves();
+    let checksum = compacted.checksum();
+    let elapsed = start.elapsed();
+    let report = options
+        .report
+        .then(|| Report::new(disk, &compacted, &moves));
+
+    match options.format {
+        Format::Text => {
+            println!("Filesystem Checksum ({}): {}", strategy.name(), checksum);
+            if let Some(report) = report {
+                println!("{}", report);
+            }
+        }
+        Format::Json => println!(
+            "{{\"day\":{},\"strategy\":{},\"answer\":{},\"elapsed_ns\":{}{}}}",
+            Day09::DAY,
+            aoc_core::json_string(strategy.name()),
+            Answer::from(checksum).to_json(),
+            elapsed.as_nanos(),
+            report.map_or(String::new(), |report| format!(
+                ",\"report\":{}",
+                report.to_json()
+            ))
+        ),
+    }
+
+    if let Some(path) = &options.trace {
+        let file = File::create(path).map_err(io_error(path))?;
+        write_trace(BufWriter::new(file), &moves).map_err(io_error(path))?;
+    }
+
+    if let Some(path) = &options.save {
+        save_disk(&compacted, path)?;
+    }
+
+    if let Some(path) = &options.render {
+        render_disk(disk, &compacted, &moves, path)?;
+    }
+
+    if let Some(path) = &options.replay {
+        let moves = parse_trace(&aoc_core::read_input(Some(Path::new(path)))?)?;
+        verify_replay(disk, &moves, &compacted)?;
+        eprintln!(
+            "Replayed {} moves from {}: matches {}",
+            moves.len(),
+            path,
+            strategy.name()
+        );
+    }
+
+    Ok(())
+}
+
+/// Writes a random disk map to stdout for `--generate <FILES>`, shaped by the
+/// other generator options.
+fn generate(files: usize, mut args: Args) -> Result<()> {
+    let mut generator = Generator::new(files, args.parsed("--seed")?.unwrap_or(0));
+    if let Some(lengths) = args.parsed("--file-lengths")? {
+        generator.file_lengths = lengths;
+    }
+    if let Some(lengths) = args.parsed("--free-lengths")? {
+        generator.free_lengths = lengths;
+    }
+    if let Some(chance) = args.parsed::<f64>("--zero-files")? {
+        if !(0.0..=1.0).contains(&chance) {
+            return Err(Error::Usage(format!(
+                "invalid value for --zero-files: {} is not between 0 and 1",
+                chance
+            )));
+        }
+        generator.zero_length_files = chance;
+    }
+    args.finish()?;
+
+    generator
+        .write(BufWriter::new(io::stdout().lock()))
+        .map_err(|source| Error::Io { path: None, source })
+}
+
+/// Compacts a volume read with `--partitions` and prints the checksum of each
+/// partition and their total: for both parts unless a strategy is chosen, or
+/// for Part Two across partitions with `--cross-partition`.
+fn run_volume(
+    volume: &Volume,
+    strategy: Option<&dyn CompactionStrategy>,
+    cross_partition: bool,
+    format: Format,
+) -> Result<()> {
+    if cross_partition && strategy.is_some_and(|strategy| strategy.name() != FirstFit.name()) {
+        return Err(Error::Usage(
+            "--cross-partition only supports the first-fit strategy".to_string(),
+        ));
+    }
+    let strategies: Vec<&dyn CompactionStrategy> = match strategy {
+        _ if cross_partition => vec![&FirstFit],
+        Some(strategy) => vec![strategy],
+        None => vec![&BlockByBlock, &FirstFit],
+    };
+
+    for strategy in strategies {
+        let start = Instant::now();
+        let mut compacted = volume.clone();
+        let name = if cross_partition {
+            compacted.compact_across_partitions();
+            "first-fit across partitions"
+        } else {
+            compacted.compact_partitions(strategy);
+            strategy.name()
+        };
+        let elapsed = start.elapsed();
+
+        let checksums = compacted.checksums();
+        match format {
+            Format::Text => {
+                for (partition, checksum) in checksums.iter().enumerate() {
+                    println!(
+                        "Partition {} Checksum ({}): {}",
+                        partition + 1,
+                        name,
+                        checksum
+                    );
+                }
+                println!("Total Checksum ({}): {}", name, compacted.checksum());
+            }
+            Format::Json => {
+                let checksums: Vec<_> = checksums
+                    .into_iter()
+                    .map(|checksum| Answer::from(checksum).to_json())
+                    .collect();
+                println!(
+                    "{{\"day\":{},\"strategy\":{},\"partitions\":[{}],\
+                     \"answer\":{},\"elapsed_ns\":{}}}",
+                    Day09::DAY,
+                    aoc_core::json_string(name),
+                    checksums.join(","),
+                    Answer::from(compacted.checksum()).to_json(),
+                    elapsed.as_nanos()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run(mut args: Args) -> Result<()> {
+    if let Some(files) = args.parsed("--generate")? {
+        return generate(files, args);
+    }
+    let format: Format = args.parsed("--format")?.unwrap_or_default();
+    let strategy = args
+        .value("--strategy")?
+        .map(|name| parse_strategy(&name))
+        .transpose()?;
+    let options = Options {
+        format,
+        report: args.flag("--report"),
+        trace: args.value("--trace")?,
+        replay: args.value("--replay")?,
+        save: args.value("--save")?,
+        render: args.value("--render")?,
+    };
+    let ids = args.value("--ids")?;
+    let cross_partition = args.flag("--cross-partition");
+    let partitions = args.flag("--partitions") || cross_partition;
+    let path = args.positional();
+    args.finish()?;
+
+    // Stream the disk map from the given file, stdin for '-', or "day_9.in"
+    let input_path = match path.as_deref() {
+        Some("-") => None,
+        Some(path) => Some(Path::new(path)),
+        None => Some(Path::new(INPUT_PATH)),
+    };
+    let input = aoc_core::open_input(input_path)?;
+    let in_input = |e: Error| match input_path {
+        Some(path) => e.in_file(path),
+        None => e,
+    };
+    // Traces, saved maps and renders follow one compaction, Part Two unless a strategy
+    // is chosen; a report or an id table alone covers both parts
+    let single = options.trace.is_some()
+        || options.replay.is_some()
+        || options.save.is_some()
+        || options.render.is_some();
+    if partitions {
+        if single || options.report || ids.is_some() {
+            return Err(Error::Usage(
+                "--partitions only reports checksums; it cannot be combined with \
+                 --trace, --replay, --save, --render, --report or --ids"
+                    .to_string(),
+            ));
+        }
+        let volume = read_volume(input, ParseMode::Strict).map_err(in_input)?;
+        return run_volume(&volume, strategy, cross_partition, format);
+    }
+
+    let start = Instant::now();
+    let mut disk = read_disk(input, ParseMode::Strict).map_err(in_input)?;
+    let parse_time = start.elapsed();
+    let strategies: Vec<&dyn CompactionStrategy> = match strategy {
+        Some(strategy) => vec![strategy],
+        None if single => vec![&FirstFit],
+        None if options.report || ids.is_some() => vec![&BlockByBlock, &FirstFit],
+        None => vec![],
+    };
+    if !strategies.is_empty() {
+        if let Some(ids) = ids {
+            let ids = parse_ids(&aoc_core::read_input(Some(Path::new(&ids)))?)?;
+            disk = relabel(&disk, &ids)?;
+        }
+        for strategy in strategies {
+            run_strategy(&disk, strategy, &options)?;
+        }
+        return Ok(());
+    }
+    if format == Format::Json {
+        let run = aoc_core::solve::<Day09>(&disk, parse_time, &[1, 2]);
+        run.print(format);
+        return run.into_result();
+    }
+
+    // Part One: Move individual blocks, then calculate the checksum
+    let checksum_part_one = Day09::part1(&disk)?;
+    println!("Part One - Filesystem Checksum: {}", checksum_part_one);
+
+    // Part Two: Move entire files, then calculate the checksum
+    let checksum_part_two = Day09::part2(&disk)?;
+    println!("Part Two - Filesystem Checksum: {}", checksum_part_two);
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    cli::report(run(Args::from_env()))
+}
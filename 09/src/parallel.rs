@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+
+use rayon::prelude::*;
+
+use crate::{compact_part_one, extent_checksum, CompactionStrategy, Disk, DiskFile};
+
+/// Free spans handed to each rayon task.
+const CHUNK: usize = 1 << 14;
+
+/// Part One computed from the layout alone, in parallel, instead of moving blocks.
+///
+/// Part One leaves every file block in the first `used` positions, `used` being
+/// the number of file blocks. Blocks already there stay put; the `k`-th free block
+/// before `used` receives the `k`-th file block counted back from the end of the
+/// disk. Prefix sums of the free span lengths give each span its `k`, so chunks
+/// of spans are filled independently.
+pub struct ParallelBlockByBlock;
+
+impl CompactionStrategy for ParallelBlockByBlock {
+    fn name(&self) -> &'static str {
+        "blocks-parallel"
+    }
+
+    /// Gives the same layout as [`compact_part_one`], which it falls back to
+    /// while the disk records moves since it makes none.
+    fn compact(&self, disk: &mut Disk) {
+        if disk.moves.is_some() {
+            compact_part_one(disk);
+            return;
+        }
+        let layout = part_one_layout(disk);
+        disk.checksum = layout.par_iter().map(extent_checksum).sum();
+        disk.files = layout.into_iter().map(|file| (file.start, file)).collect();
+        disk.free = BTreeMap::new();
+        if disk.used < disk.len {
+            disk.free.insert(disk.used, disk.len - disk.used);
+        }
+    }
+}
+
+/// The checksum [`compact_part_one`] would lead to, without building the layout.
+/// Blocks that stay put are never visited: the disk's checksum already covers
+/// them once the blocks that move are taken out of it.
+pub fn part_one_checksum(disk: &Disk) -> u128 {
+    let plan = Plan::new(disk);
+    let moved: u128 = plan.tail.par_iter().map(extent_checksum).sum();
+    let filled: u128 = plan
+        .fill_chunks()
+        .map(|(holes, first_block)| {
+            let mut checksum = 0;
+            plan.fill(holes, first_block, |file| {
+                checksum += extent_checksum(&file)
+            });
+            checksum
+        })
+        .sum();
+    disk.checksum - moved + filled
+}
+
+/// The file extents after [`compact_part_one`], in disk order, with neighbouring
+/// extents of the same file merged.
+pub fn part_one_layout(disk: &Disk) -> Vec<DiskFile> {
+    let plan = Plan::new(disk);
+    let kept: Vec<DiskFile> = disk
+        .files
+        .range(..disk.used)
+        .map(|(_, file)| DiskFile {
+            id: file.id,
+            start: file.start,
+            length: file.end().min(disk.used) - file.start,
+        })
+        .collect();
+    let mut pieces: Vec<DiskFile> = plan
+        .fill_chunks()
+        .flat_map_iter(|(holes, first_block)| {
+            let mut pieces = Vec::new();
+            plan.fill(holes, first_block, |file| pieces.push(file));
+            pieces
+        })
+        .chain(kept.into_par_iter())
+        .collect();
+    pieces.par_sort_unstable_by_key(|file| file.start);
+
+    let mut layout: Vec<DiskFile> = Vec::with_capacity(pieces.len());
+    for piece in pieces {
+        match layout.last_mut() {
+            Some(last) if last.id == piece.id && last.end() == piece.start => {
+                last.length += piece.length;
+            }
+            _ => layout.push(piece),
+        }
+    }
+    layout
+}
+
+/// Where Part One's moving blocks come from and go to. `used` is the number of
+/// file blocks, so Part One leaves every block before it full.
+struct Plan {
+    /// Free spans, or their parts, before `used`, as `(start, length)`.
+    holes: Vec<(usize, usize)>,
+    /// Free blocks before each chunk of `holes`, which is also how many blocks
+    /// of `tail` the chunks before it take.
+    chunk_starts: Vec<usize>,
+    /// File extents, or their parts, from `used` on, last extent first.
+    tail: Vec<DiskFile>,
+    /// Running total of `tail` lengths, up to and including each extent.
+    tail_ends: Vec<usize>,
+}
+
+impl Plan {
+    fn new(disk: &Disk) -> Self {
+        let used = disk.used;
+        let holes: Vec<(usize, usize)> = disk
+            .free
+            .range(..used)
+            .map(|(&start, &length)| (start, length.min(used - start)))
+            .collect();
+
+        // Prefix sums of free blocks, per chunk and then across chunks
+        let chunk_totals: Vec<usize> = holes
+            .par_chunks(CHUNK)
+            .map(|chunk| chunk.iter().map(|&(_, length)| length).sum())
+            .collect();
+        let chunk_starts = exclusive_prefix_sums(&chunk_totals);
+
+        let tail: Vec<DiskFile> = disk
+            .files
+            .values()
+            .rev()
+            .take_while(|file| file.end() > used)
+            .map(|file| DiskFile {
+                id: file.id,
+                start: file.start.max(used),
+                length: file.end() - file.start.max(used),
+            })
+            .collect();
+        let tail_lengths: Vec<usize> = tail.iter().map(|file| file.length).collect();
+        let tail_ends = exclusive_prefix_sums(&tail_lengths)
+            .into_iter()
+            .zip(&tail_lengths)
+            .map(|(start, length)| start + length)
+            .collect();
+
+        Plan {
+            holes,
+            chunk_starts,
+            tail,
+            tail_ends,
+        }
+    }
+
+    /// Chunks of holes with the number of free blocks before each.
+    fn fill_chunks(&self) -> impl IndexedParallelIterator<Item = (&[(usize, usize)], usize)> {
+        self.holes
+            .par_chunks(CHUNK)
+            .zip(self.chunk_starts.par_iter().copied())
+    }
+
+    /// Passes each extent moved into `holes` to `emit`, in disk order. The first
+    /// block of `holes` is the `first_block`-th free block before `used`.
+    fn fill<F: FnMut(DiskFile)>(&self, holes: &[(usize, usize)], first_block: usize, mut emit: F) {
+        let mut block = first_block;
+        let mut source = self.tail_ends.partition_point(|&end| end <= block);
+        for &(start, length) in holes {
+            let end = start + length;
+            let mut pos = start;
+            while pos < end {
+                let source_end = self.tail_ends[source];
+                let taken = (source_end - block).min(end - pos);
+                emit(DiskFile {
+                    id: self.tail[source].id,
+                    start: pos,
+                    length: taken,
+                });
+                pos += taken;
+                block += taken;
+                if block == source_end {
+                    source += 1;
+                }
+            }
+        }
+    }
+}
+
+/// `[a, b, c]` becomes `[0, a, a + b]`.
+fn exclusive_prefix_sums(values: &[usize]) -> Vec<usize> {
+    values
+        .iter()
+        .scan(0, |total, &value| {
+            let start = *total;
+            *total += value;
+            Some(start)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{identify_files, parse_disk_map, Generator};
+
+    #[test]
+    fn test_part_one_in_parallel() {
+        for disk_map in ["12345", "2333133121414131402", "", "0", "90909", "1919"] {
+            let disk = parse_disk_map(disk_map);
+            let mut expected = disk.clone();
+            compact_part_one(&mut expected);
+
+            let mut parallel = disk.clone();
+            ParallelBlockByBlock.compact(&mut parallel);
+            assert_eq!(parallel, expected, "{}", disk_map);
+            assert_eq!(parallel.checksum(), expected.checksum(), "{}", disk_map);
+            assert_eq!(
+                part_one_checksum(&disk),
+                expected.checksum(),
+                "{}",
+                disk_map
+            );
+            assert_eq!(part_one_layout(&disk), identify_files(&expected));
+        }
+
+        // More than one chunk of free spans, and a tail of split files
+        let disk = parse_disk_map(&Generator::new(50_000, 5).generate());
+        let mut expected = disk.clone();
+        compact_part_one(&mut expected);
+        assert_eq!(part_one_checksum(&disk), expected.checksum());
+        assert_eq!(part_one_layout(&disk), identify_files(&expected));
+
+        // A disk that records moves is compacted one move at a time
+        let mut recorded = parse_disk_map("12345");
+        recorded.record_moves();
+        ParallelBlockByBlock.compact(&mut recorded);
+        assert_eq!(recorded.take_moves().len(), 2);
+    }
+
+    #[test]
+    #[ignore = "a million-digit map takes too long unoptimised; run with --release -- --ignored"]
+    fn test_part_one_in_parallel_million_digit_map() {
+        // Half a million files, a 1,000,000-digit map of about 4.75 million blocks
+        let disk = parse_disk_map(&Generator::new(500_000, 5).generate());
+        let mut expected = disk.clone();
+        compact_part_one(&mut expected);
+        assert_eq!(part_one_checksum(&disk), expected.checksum());
+        assert_eq!(part_one_layout(&disk), identify_files(&expected));
+    }
+}
@@ -0,0 +1,192 @@
+//! Property tests for the compaction invariants, over random disk maps.
+
+use std::collections::BTreeMap;
+
+use proptest::prelude::*;
+
+use super::*;
+
+/// Disk maps of up to 200 digits, with zero-length files and free spans
+/// more common than in the puzzle input.
+fn disk_maps() -> impl Strategy<Value = String> {
+    let digit = prop_oneof![1 => Just(0u8), 3 => 0..=9u8];
+    prop::collection::vec(digit, 0..200)
+        .prop_map(|digits| digits.iter().map(|&d| char::from(b'0' + d)).collect())
+}
+
+/// Number of blocks of each file.
+fn block_counts(disk: &Disk) -> BTreeMap<usize, usize> {
+    let mut counts = BTreeMap::new();
+    for file in disk.extents() {
+        *counts.entry(file.id).or_default() += file.length;
+    }
+    counts
+}
+
+/// Extents of each file, in disk order.
+fn extents_by_id(disk: &Disk) -> BTreeMap<usize, Vec<DiskFile>> {
+    let mut extents: BTreeMap<usize, Vec<DiskFile>> = BTreeMap::new();
+    for file in disk.extents() {
+        extents.entry(file.id).or_default().push(file.clone());
+    }
+    extents
+}
+
+proptest! {
+    #[test]
+    fn every_strategy_keeps_the_file_blocks(disk_map in disk_maps()) {
+        let disk = parse_disk_map(&disk_map);
+        for strategy in STRATEGIES {
+            let mut compacted = disk.clone();
+            strategy.compact(&mut compacted);
+            prop_assert_eq!(compacted.len(), disk.len(), "{}", strategy.name());
+            prop_assert_eq!(
+                block_counts(&compacted),
+                block_counts(&disk),
+                "{}",
+                strategy.name()
+            );
+        }
+    }
+
+    #[test]
+    fn compacting_strategies_never_move_blocks_right(disk_map in disk_maps()) {
+        let disk = parse_disk_map(&disk_map);
+        let compacting = STRATEGIES
+            .iter()
+            .filter(|strategy| !matches!(strategy.name(), "pack-right" | "spread-evenly"));
+        for strategy in compacting {
+            let mut compacted = disk.clone();
+            compacted.record_moves();
+            strategy.compact(&mut compacted);
+            for m in compacted.take_moves() {
+                prop_assert!(m.to < m.from, "{}: {:?}", strategy.name(), m);
+            }
+
+            // So no file ends further right than it did
+            let before = extents_by_id(&disk);
+            for (id, extents) in extents_by_id(&compacted) {
+                let end = |extents: &[DiskFile]| extents.last().map(DiskFile::end);
+                prop_assert!(
+                    end(&extents) <= end(&before[&id]),
+                    "{}: file {}",
+                    strategy.name(),
+                    id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn checksum_is_maintained_through_every_move(disk_map in disk_maps()) {
+        let disk = parse_disk_map(&disk_map);
+        prop_assert_eq!(disk.checksum(), calculate_checksum(&disk));
+        for strategy in STRATEGIES {
+            let mut compacted = disk.clone();
+            compacted.record_moves();
+            strategy.compact(&mut compacted);
+            prop_assert_eq!(compacted.checksum(), calculate_checksum(&compacted));
+
+            // Replaying one move at a time checks every intermediate checksum
+            let mut replayed = disk.clone();
+            for m in compacted.take_moves() {
+                replay(&mut replayed, &[m]).unwrap();
+                prop_assert_eq!(m.checksum, Some(calculate_checksum(&replayed)));
+            }
+        }
+    }
+
+    #[test]
+    fn part_two_never_splits_a_file(disk_map in disk_maps()) {
+        let disk = parse_disk_map(&disk_map);
+        let mut compacted = disk.clone();
+        compact_part_two(&mut compacted);
+        for (id, extents) in extents_by_id(&compacted) {
+            prop_assert_eq!(extents.len(), 1, "file {}", id);
+            prop_assert!(extents[0].start <= extents_by_id(&disk)[&id][0].start);
+        }
+    }
+
+    #[test]
+    fn pack_right_never_moves_files_left_or_splits_them(disk_map in disk_maps()) {
+        let disk = parse_disk_map(&disk_map);
+        let mut packed = disk.clone();
+        packed.record_moves();
+        PackRight.compact(&mut packed);
+        for m in packed.take_moves() {
+            prop_assert!(m.to > m.from, "{:?}", m);
+        }
+        prop_assert_eq!(packed.extents().count(), disk.extents().count());
+    }
+
+    #[test]
+    fn spread_evenly_keeps_the_order_and_evens_the_gaps(disk_map in disk_maps()) {
+        let disk = parse_disk_map(&disk_map);
+        let mut spread = disk.clone();
+        SpreadEvenly.compact(&mut spread);
+
+        let ids = |disk: &Disk| disk.extents().map(|file| file.id).collect::<Vec<_>>();
+        prop_assert_eq!(ids(&spread), ids(&disk));
+        let extents: Vec<_> = spread.extents().collect();
+        if let Some(first) = extents.first() {
+            prop_assert_eq!(first.start, 0);
+        }
+        if extents.len() > 1 {
+            prop_assert_eq!(extents[extents.len() - 1].end(), spread.len());
+            let gaps: Vec<_> = extents.windows(2).map(|w| w[1].start - w[0].end()).collect();
+            prop_assert!(gaps.windows(2).all(|w| w[0] == w[1] || w[0] == w[1] + 1), "{:?}", gaps);
+        }
+    }
+
+    #[test]
+    fn part_one_leaves_free_space_at_the_end(disk_map in disk_maps()) {
+        let mut disk = parse_disk_map(&disk_map);
+        compact_part_one(&mut disk);
+        let blocks = disk.to_blocks();
+        let used = blocks.iter().filter(|block| block.is_some()).count();
+        prop_assert!(blocks[..used].iter().all(Option::is_some));
+        prop_assert!(disk.free_spans().all(|(start, _)| start >= used));
+    }
+
+    #[test]
+    fn parallel_part_one_matches_compact_part_one(disk_map in disk_maps()) {
+        let disk = parse_disk_map(&disk_map);
+        let mut expected = disk.clone();
+        compact_part_one(&mut expected);
+        prop_assert_eq!(part_one_checksum(&disk), expected.checksum());
+        prop_assert_eq!(part_one_layout(&disk), identify_files(&expected));
+    }
+
+    #[test]
+    fn checksum_matches_a_block_scan(disk_map in disk_maps()) {
+        let mut disk = parse_disk_map(&disk_map);
+        compact_part_two(&mut disk);
+        let expected: u128 = disk
+            .to_blocks()
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, block)| block.map(|id| (pos * id) as u128))
+            .sum();
+        prop_assert_eq!(calculate_checksum(&disk), expected);
+    }
+
+    #[test]
+    fn zero_length_files_take_no_space(disk_map in disk_maps()) {
+        let disk = parse_disk_map(&disk_map);
+        let files = disk_map.bytes().step_by(2).filter(|&digit| digit != b'0').count();
+        prop_assert_eq!(disk.extents().count(), files);
+        prop_assert!(disk.extents().all(|file| file.length > 0));
+    }
+
+    #[test]
+    fn maps_round_trip(disk_map in disk_maps()) {
+        let disk = parse_disk_map(&disk_map);
+        prop_assert_eq!(&read_disk(disk_map.as_bytes(), ParseMode::Strict).unwrap(), &disk);
+        prop_assert_eq!(&Disk::from_blocks(&disk.to_blocks()), &disk);
+        prop_assert_eq!(&encode_disk(&disk).decode().unwrap(), &disk);
+
+        let mut compacted = disk.clone();
+        compact_part_two(&mut compacted);
+        prop_assert_eq!(&encode_disk(&compacted).decode().unwrap(), &compacted);
+    }
+}
@@ -0,0 +1,323 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, Write};
+
+use gif::{DisposalMethod, Encoder, Frame, Repeat};
+
+use crate::{Disk, Move};
+
+/// Height in pixels of a heatmap written by [`write_png`] unless one is given.
+pub const HEATMAP_HEIGHT: u32 = 32;
+
+/// Blocks per row of an animation frame; longer disks wrap onto more rows.
+const GIF_COLUMNS: usize = 1000;
+
+/// Frames of short disks are scaled up to at least this many pixels across.
+const GIF_MIN_WIDTH: usize = 400;
+
+/// Delay between animation frames, in hundredths of a second.
+const GIF_DELAY: u16 = 2;
+
+/// The puzzle's notation, one character per block: ids 0-9 as digits, 10-35
+/// as the letters a-z, '#' for higher ids and '.' for free space.
+impl fmt::Display for Disk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut pos = 0;
+        for file in self.extents() {
+            for _ in pos..file.start {
+                f.write_str(".")?;
+            }
+            let symbol = u32::try_from(file.id)
+                .ok()
+                .and_then(|id| char::from_digit(id, 36))
+                .unwrap_or('#');
+            for _ in 0..file.length {
+                write!(f, "{}", symbol)?;
+            }
+            pos = file.end();
+        }
+        for _ in pos..self.len() {
+            f.write_str(".")?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the disk as a PNG heatmap `height` pixels tall, one pixel column per
+/// block. Files run from blue for id 0 to red for the highest id; free space is black.
+pub fn write_png<W: Write>(writer: W, disk: &Disk, height: u32) -> io::Result<()> {
+    let width = u32::try_from(disk.len())
+        .ok()
+        .filter(|&width| width > 0)
+        .ok_or_else(|| io::Error::other("the disk is empty or too large for a PNG"))?;
+    let max_id = max_id(disk);
+    let mut row = vec![0; disk.len() * 3];
+    for file in disk.extents() {
+        let colour = heat(file.id, max_id);
+        for pixel in row[file.start * 3..file.end() * 3].chunks_exact_mut(3) {
+            pixel.copy_from_slice(&colour);
+        }
+    }
+
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    let mut stream = writer.stream_writer()?;
+    for _ in 0..height {
+        stream.write_all(&row)?;
+    }
+    stream.finish()?;
+    Ok(())
+}
+
+/// Writes an animated GIF of `moves` being applied to `initial`, one step per
+/// move after a first frame of the initial disk. The moves are drawn as given,
+/// not checked against the files on the disk; use [`replay`](crate::replay)
+/// for that. Moves that run off the disk are an error.
+///
+/// Blocks wrap into rows of up to 1000, drawn as squares large enough that
+/// short disks stay visible. Colours follow [`write_png`], reduced to a
+/// 256-colour palette, so neighbouring ids may share a colour on large disks.
+pub fn write_gif<W: Write>(writer: W, initial: &Disk, moves: &[Move]) -> io::Result<()> {
+    let columns = initial.len().clamp(1, GIF_COLUMNS);
+    let rows = initial.len().div_ceil(columns).max(1);
+    let scale = (GIF_MIN_WIDTH / columns).max(1);
+    let (width, height) = match (u16::try_from(columns * scale), u16::try_from(rows * scale)) {
+        (Ok(width), Ok(height)) => (width, height),
+        _ => return Err(io::Error::other("the disk is too large for a GIF")),
+    };
+
+    let max_id = max_id(initial);
+    let mut palette = vec![0; 3];
+    for index in 1..=255 {
+        palette.extend_from_slice(&heat(index - 1, 254));
+    }
+    let colour_index = |id: usize| (1 + id * 254 / max_id.max(1)) as u8;
+
+    // Palette index of every block, updated as each move is drawn
+    let mut blocks = vec![0; initial.len()];
+    for file in initial.extents() {
+        blocks[file.start..file.end()].fill(colour_index(file.id));
+    }
+
+    let mut encoder = Encoder::new(writer, width, height, &palette).map_err(gif_error)?;
+    encoder.set_repeat(Repeat::Infinite).map_err(gif_error)?;
+    let grid = Grid {
+        columns,
+        scale,
+        width: width.into(),
+    };
+    encoder
+        .write_frame(&grid.frame(&blocks, 0..rows))
+        .map_err(gif_error)?;
+
+    for (number, m) in moves.iter().enumerate() {
+        let on_disk = |start: usize| {
+            m.length > 0
+                && start
+                    .checked_add(m.length)
+                    .is_some_and(|end| end <= blocks.len())
+        };
+        if !on_disk(m.from) || !on_disk(m.to) {
+            return Err(io::Error::other(format!(
+                "move {} does not fit on a disk of {} blocks",
+                number + 1,
+                blocks.len()
+            )));
+        }
+        blocks[m.from..m.from + m.length].fill(0);
+        blocks[m.to..m.to + m.length].fill(colour_index(m.file_id));
+
+        // Only the rows the move touches are redrawn: those it was taken from
+        // in a frame shown for no time, then those it was put in
+        let rows_of = |start: usize| start / columns..(start + m.length - 1) / columns + 1;
+        let (from, to) = (rows_of(m.from), rows_of(m.to));
+        if from.start <= to.end && to.start <= from.end {
+            let rows = from.start.min(to.start)..from.end.max(to.end);
+            encoder
+                .write_frame(&grid.frame(&blocks, rows))
+                .map_err(gif_error)?;
+        } else {
+            let mut taken = grid.frame(&blocks, from);
+            taken.delay = 0;
+            encoder.write_frame(&taken).map_err(gif_error)?;
+            encoder
+                .write_frame(&grid.frame(&blocks, to))
+                .map_err(gif_error)?;
+        }
+    }
+    encoder.into_inner().map_err(gif_error)?.flush()
+}
+
+/// How blocks are laid out in an animation frame.
+struct Grid {
+    columns: usize,
+    /// Side of each block's square, in pixels.
+    scale: usize,
+    /// Width of the frame in pixels.
+    width: usize,
+}
+
+impl Grid {
+    /// A frame covering the given rows of blocks, drawn over the previous frame.
+    fn frame(&self, blocks: &[u8], rows: std::ops::Range<usize>) -> Frame<'static> {
+        let mut buffer = Vec::with_capacity(rows.len() * self.scale * self.width);
+        for row in rows.clone() {
+            let mut line = Vec::with_capacity(self.width);
+            for column in 0..self.columns {
+                let index = blocks.get(row * self.columns + column).copied();
+                line.extend(std::iter::repeat_n(index.unwrap_or(0), self.scale));
+            }
+            for _ in 0..self.scale {
+                buffer.extend_from_slice(&line);
+            }
+        }
+        Frame {
+            delay: GIF_DELAY,
+            dispose: DisposalMethod::Keep,
+            top: (rows.start * self.scale) as u16,
+            width: self.width as u16,
+            height: (rows.len() * self.scale) as u16,
+            buffer: Cow::Owned(buffer),
+            ..Frame::default()
+        }
+    }
+}
+
+fn max_id(disk: &Disk) -> usize {
+    disk.extents().map(|file| file.id).max().unwrap_or(0)
+}
+
+/// Colour of file `id` on a scale from blue for 0 to red for `max_id`, by hue.
+fn heat(id: usize, max_id: usize) -> [u8; 3] {
+    let t = if max_id == 0 {
+        0.0
+    } else {
+        id as f64 / max_id as f64
+    };
+    // Hue sectors from red (0) through yellow, green and cyan to blue (4)
+    let hue = (1.0 - t) * 4.0;
+    let x = ((1.0 - (hue % 2.0 - 1.0).abs()) * 255.0).round() as u8;
+    match hue as u32 {
+        0 => [255, x, 0],
+        1 => [x, 255, 0],
+        2 => [0, 255, x],
+        _ => [0, x, 255],
+    }
+}
+
+fn gif_error(error: gif::EncodingError) -> io::Error {
+    match error {
+        gif::EncodingError::Io(error) => error,
+        error => io::Error::other(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compact_part_two, parse_disk_map, Generator};
+
+    #[test]
+    fn test_render_text() {
+        let disk = parse_disk_map("2333133121414131402");
+        assert_eq!(
+            disk.to_string(),
+            "00...111...2...333.44.5555.6666.777.888899"
+        );
+        assert_eq!(Disk::default().to_string(), "");
+
+        // Ids past 'z' have no character of their own
+        let disk = Disk::from_blocks(&[Some(10), None, Some(35), Some(36)]);
+        assert_eq!(disk.to_string(), "a.z#");
+    }
+
+    #[test]
+    fn test_write_png_heatmap() {
+        let disk = parse_disk_map("12345");
+        let mut png = Vec::new();
+        write_png(&mut png, &disk, 2).unwrap();
+
+        let mut reader = png::Decoder::new(std::io::Cursor::new(png))
+            .read_info()
+            .unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (15, 2));
+
+        // One column per block: blue for file 0, black for free space, red for file 2
+        let row = &pixels[..info.line_size];
+        assert_eq!(row[0..3], [0, 0, 255]);
+        assert_eq!(row[3..6], [0, 0, 0]);
+        assert_eq!(row[9..12], [0, 255, 0]);
+        assert_eq!(row[42..45], [255, 0, 0]);
+        assert_eq!(pixels[info.line_size..], *row);
+    }
+
+    #[test]
+    fn test_write_gif_frames() {
+        let mut disk = parse_disk_map("2333133121414131402");
+        let initial = disk.clone();
+        disk.record_moves();
+        compact_part_two(&mut disk);
+        let moves = disk.take_moves();
+
+        let mut gif = Vec::new();
+        write_gif(&mut gif, &initial, &moves).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        // 42 blocks are scaled up to squares of 9 pixels
+        assert_eq!((decoder.width(), decoder.height()), (378, 9));
+        // The whole disk is one row, so every move is drawn in a single frame
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 2);
+            frames += 1;
+        }
+        assert_eq!(frames, moves.len() + 1);
+
+        // Moves off the end of the disk are errors, not panics
+        let mut off_disk = moves.clone();
+        off_disk[1].to = 41;
+        let err = write_gif(&mut Vec::new(), &initial, &off_disk).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "move 2 does not fit on a disk of 42 blocks"
+        );
+        off_disk[1].to = usize::MAX;
+        assert!(write_gif(&mut Vec::new(), &initial, &off_disk).is_err());
+    }
+
+    #[test]
+    fn test_write_gif_redraws_only_touched_rows() {
+        let mut disk = parse_disk_map(&Generator::new(500, 9).generate());
+        let initial = disk.clone();
+        disk.record_moves();
+        compact_part_two(&mut disk);
+        let moves = disk.take_moves();
+        assert!(initial.len() > 2000);
+
+        let mut gif = Vec::new();
+        write_gif(&mut gif, &initial, &moves).unwrap();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        assert_eq!(decoder.width(), 1000);
+        let height = decoder.height();
+
+        // Moves between rows that are far apart take a second, instant frame
+        let (mut steps, mut frames) = (0, 0);
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert!(frame.top + frame.height <= height);
+            if frame.delay > 0 {
+                steps += 1;
+            }
+            frames += 1;
+        }
+        assert_eq!(steps, moves.len() + 1);
+        assert!(frames > steps);
+    }
+}
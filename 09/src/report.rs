@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::{Disk, Move};
+
+/// Move and fragmentation statistics comparing a disk before and after compaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// Files moved at least once.
+    pub files_moved: usize,
+    /// Blocks moved, summed over every move, so a block moved twice counts twice.
+    pub blocks_moved: usize,
+    /// Files never moved.
+    pub files_in_place: usize,
+    pub free_extents_before: usize,
+    pub free_extents_after: usize,
+    /// Length of the largest free span after compaction.
+    pub largest_free_extent: usize,
+    /// `1 - largest / total` free space after compaction: 0 when all free space is
+    /// one span, approaching 1 as it is scattered over many small ones.
+    pub fragmentation: f64,
+}
+
+impl Report {
+    /// Compares `before` with `after`, which `moves` turned it into, as
+    /// recorded by [`Disk::record_moves`].
+    pub fn new(before: &Disk, after: &Disk, moves: &[Move]) -> Self {
+        let files: HashSet<usize> = before.extents().map(|file| file.id).collect();
+        let files_moved = moves
+            .iter()
+            .map(|m| m.file_id)
+            .collect::<HashSet<_>>()
+            .len();
+        let blocks_moved = moves.iter().map(|m| m.length).sum();
+
+        let total_free: usize = after.free_spans().map(|(_, length)| length).sum();
+        let largest_free_extent = after
+            .free_spans()
+            .map(|(_, length)| length)
+            .max()
+            .unwrap_or(0);
+        let fragmentation = if total_free == 0 {
+            0.0
+        } else {
+            1.0 - largest_free_extent as f64 / total_free as f64
+        };
+
+        Report {
+            files_moved,
+            blocks_moved,
+            files_in_place: files.len() - files_moved,
+            free_extents_before: before.free_spans().count(),
+            free_extents_after: after.free_spans().count(),
+            largest_free_extent,
+            fragmentation,
+        }
+    }
+
+    /// The report as a JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"files_moved\":{},\"blocks_moved\":{},\"files_in_place\":{},\
+             \"free_extents_before\":{},\"free_extents_after\":{},\
+             \"largest_free_extent\":{},\"fragmentation\":{:.6}}}",
+            self.files_moved,
+            self.blocks_moved,
+            self.files_in_place,
+            self.free_extents_before,
+            self.free_extents_after,
+            self.largest_free_extent,
+            self.fragmentation
+        )
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  files moved:         {}", self.files_moved)?;
+        writeln!(f, "  blocks moved:        {}", self.blocks_moved)?;
+        writeln!(f, "  files left in place: {}", self.files_in_place)?;
+        writeln!(
+            f,
+            "  free extents:        {} before, {} after",
+            self.free_extents_before, self.free_extents_after
+        )?;
+        writeln!(
+            f,
+            "  largest free extent: {} blocks",
+            self.largest_free_extent
+        )?;
+        write!(f, "  fragmentation:       {:.3}", self.fragmentation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_disk_map, strategy};
+
+    /// Compacts `disk_map` with the named strategy and reports on it.
+    fn report_for(name: &str, disk_map: &str) -> Report {
+        let before = parse_disk_map(disk_map);
+        let mut after = before.clone();
+        after.record_moves();
+        strategy(name).unwrap().compact(&mut after);
+        let moves = after.take_moves();
+        Report::new(&before, &after, &moves)
+    }
+
+    #[test]
+    fn test_report_block_by_block() {
+        // 0..111....22222 becomes 022111222......
+        let report = report_for("blocks", "12345");
+        assert_eq!(report.files_moved, 1);
+        assert_eq!(report.blocks_moved, 5);
+        assert_eq!(report.files_in_place, 2);
+        assert_eq!(report.free_extents_before, 2);
+        assert_eq!(report.free_extents_after, 1);
+        assert_eq!(report.largest_free_extent, 6);
+        assert_eq!(report.fragmentation, 0.0);
+    }
+
+    #[test]
+    fn test_report_first_fit() {
+        // 00...111...2...333.44.5555.6666.777.888899 becomes
+        // 00992111777.44.333....5555.6666.....8888..
+        let report = report_for("first-fit", "2333133121414131402");
+        // Files 9, 2, 7 and 4 move; the rest stay
+        assert_eq!(report.files_moved, 4);
+        assert_eq!(report.blocks_moved, 2 + 1 + 3 + 2);
+        assert_eq!(report.files_in_place, 6);
+        assert_eq!(report.free_extents_before, 8);
+        assert_eq!(report.free_extents_after, 6);
+        assert_eq!(report.largest_free_extent, 5);
+        assert!((report.fragmentation - (1.0 - 5.0 / 14.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_report_counts_every_block_moved() {
+        // 0..111: split-once moves two blocks of file 1 into the gap,
+        // then its last block into the space they left
+        let report = report_for("split-once", "123");
+        assert_eq!(report.files_moved, 1);
+        assert_eq!(report.blocks_moved, 3);
+        assert_eq!(report.files_in_place, 1);
+
+        // 0...1112 becomes 0..111.2: file 1 shifts left by one, onto
+        // blocks it already held, and all three of its blocks move
+        let report = report_for("spread-evenly", "13301");
+        assert_eq!(report.files_moved, 1);
+        assert_eq!(report.blocks_moved, 3);
+        assert_eq!(report.files_in_place, 2);
+    }
+}
@@ -0,0 +1,335 @@
+use std::cmp::Reverse;
+
+use crate::{
+    compact_part_one, compact_part_two, find_leftmost_free_span, find_rightmost_free_span,
+    identify_files, move_file, move_files_first_fit, Disk, DiskFile, ParallelBlockByBlock,
+};
+
+/// A policy for moving file blocks into free space.
+pub trait CompactionStrategy {
+    /// Name used to select the strategy on the command line.
+    fn name(&self) -> &'static str;
+
+    /// Compacts `disk` in place.
+    fn compact(&self, disk: &mut Disk);
+}
+
+/// Part One: moves single blocks from the end of the disk into the first gap.
+pub struct BlockByBlock;
+
+/// Part Two: moves whole files, highest id first, into the leftmost span that fits.
+pub struct FirstFit;
+
+/// Moves whole files, highest id first, into the smallest span that fits.
+pub struct BestFit;
+
+/// Moves whole files, highest id first, into the largest span that fits.
+pub struct WorstFit;
+
+/// Like [`FirstFit`], but a file that fits nowhere whole may be split once: its
+/// head fills the leftmost gap before it and its tail moves to the leftmost span
+/// that fits the rest, or stays where it is.
+pub struct FirstFitSplitOnce;
+
+/// Like [`FirstFit`], but moves the largest files first.
+pub struct SizeOrder;
+
+/// Part Two mirrored: moves whole files, lowest id first, to the end of the
+/// rightmost span after them that fits, so data gathers at the end of the disk.
+pub struct PackRight;
+
+/// Keeps files in disk order but spreads the free space evenly between them,
+/// from the start of the disk to its end. Gaps differ by at most one block,
+/// the longer ones first.
+pub struct SpreadEvenly;
+
+/// Every strategy, in the order they are listed on the command line.
+pub const STRATEGIES: &[&dyn CompactionStrategy] = &[
+    &BlockByBlock,
+    &ParallelBlockByBlock,
+    &FirstFit,
+    &BestFit,
+    &WorstFit,
+    &FirstFitSplitOnce,
+    &SizeOrder,
+    &PackRight,
+    &SpreadEvenly,
+];
+
+/// Looks up a strategy by name.
+pub fn strategy(name: &str) -> Option<&'static dyn CompactionStrategy> {
+    STRATEGIES
+        .iter()
+        .copied()
+        .find(|strategy| strategy.name() == name)
+}
+
+impl CompactionStrategy for BlockByBlock {
+    fn name(&self) -> &'static str {
+        "blocks"
+    }
+
+    fn compact(&self, disk: &mut Disk) {
+        compact_part_one(disk);
+    }
+}
+
+impl CompactionStrategy for FirstFit {
+    fn name(&self) -> &'static str {
+        "first-fit"
+    }
+
+    fn compact(&self, disk: &mut Disk) {
+        compact_part_two(disk);
+    }
+}
+
+impl CompactionStrategy for BestFit {
+    fn name(&self) -> &'static str {
+        "best-fit"
+    }
+
+    fn compact(&self, disk: &mut Disk) {
+        // Smallest fitting span; min_by_key keeps the leftmost of equal spans
+        move_files_by_span(disk, |spans| spans.min_by_key(|&(_, length)| length));
+    }
+}
+
+impl CompactionStrategy for WorstFit {
+    fn name(&self) -> &'static str {
+        "worst-fit"
+    }
+
+    fn compact(&self, disk: &mut Disk) {
+        // Largest fitting span; the reversed key keeps the leftmost of equal spans
+        move_files_by_span(disk, |spans| {
+            spans.min_by_key(|&(_, length)| Reverse(length))
+        });
+    }
+}
+
+impl CompactionStrategy for FirstFitSplitOnce {
+    fn name(&self) -> &'static str {
+        "split-once"
+    }
+
+    fn compact(&self, disk: &mut Disk) {
+        for file in files_by_id_descending(disk) {
+            if let Some(target_start) = find_leftmost_free_span(disk, file.length, file.start) {
+                move_file(disk, file.id, file.start, target_start, file.length);
+                continue;
+            }
+
+            // Nothing fits the whole file: fill the leftmost gap before it with its head
+            let Some((gap_start, gap_length)) = disk
+                .free_spans()
+                .next()
+                .filter(|&(start, _)| start < file.start)
+            else {
+                continue;
+            };
+            let head = gap_length.min(file.length);
+            move_file(disk, file.id, file.start, gap_start, head);
+
+            // The tail moves as one piece or not at all
+            let (tail_start, tail_length) = (file.start + head, file.length - head);
+            if let Some(target_start) = find_leftmost_free_span(disk, tail_length, tail_start) {
+                move_file(disk, file.id, tail_start, target_start, tail_length);
+            }
+        }
+    }
+}
+
+impl CompactionStrategy for SizeOrder {
+    fn name(&self) -> &'static str {
+        "size-order"
+    }
+
+    fn compact(&self, disk: &mut Disk) {
+        let mut files = identify_files(disk);
+        // Largest first, ties broken like Part Two by highest id
+        files.sort_by_key(|file| Reverse((file.length, file.id)));
+        move_files_first_fit(disk, files);
+    }
+}
+
+impl CompactionStrategy for PackRight {
+    fn name(&self) -> &'static str {
+        "pack-right"
+    }
+
+    fn compact(&self, disk: &mut Disk) {
+        let mut files = identify_files(disk);
+        files.sort_by_key(|file| file.id);
+        for file in files {
+            if let Some(target_start) = find_rightmost_free_span(disk, file.length, file.end()) {
+                move_file(disk, file.id, file.start, target_start, file.length);
+            }
+        }
+    }
+}
+
+impl CompactionStrategy for SpreadEvenly {
+    fn name(&self) -> &'static str {
+        "spread-evenly"
+    }
+
+    fn compact(&self, disk: &mut Disk) {
+        let files = identify_files(disk);
+        let used: usize = files.iter().map(|file| file.length).sum();
+        let free = disk.len() - used;
+        let gaps = files.len().saturating_sub(1).max(1);
+
+        let mut targets = Vec::with_capacity(files.len());
+        let mut pos = 0;
+        for (i, file) in files.iter().enumerate() {
+            targets.push(pos);
+            pos += file.length + free / gaps + usize::from(i < free % gaps);
+        }
+
+        // Files moving left go first, leftmost first, so each lands on space
+        // its left neighbour has already vacated; then those moving right,
+        // rightmost first
+        let moves: Vec<_> = files.iter().zip(targets).collect();
+        let left = moves.iter().filter(|(file, target)| *target < file.start);
+        let right = moves
+            .iter()
+            .rev()
+            .filter(|(file, target)| *target > file.start);
+        for (file, target) in left.chain(right) {
+            move_file(disk, file.id, file.start, *target, file.length);
+        }
+    }
+}
+
+fn files_by_id_descending(disk: &Disk) -> Vec<DiskFile> {
+    let mut files = identify_files(disk);
+    files.sort_by_key(|file| Reverse(file.id));
+    files
+}
+
+/// Moves whole files, highest id first, into the span `choose` picks among the
+/// free spans before each file that are large enough to hold it.
+fn move_files_by_span<F>(disk: &mut Disk, choose: F)
+where
+    F: Fn(&mut dyn Iterator<Item = (usize, usize)>) -> Option<(usize, usize)>,
+{
+    for file in files_by_id_descending(disk) {
+        let target = {
+            let mut spans = disk
+                .free_spans()
+                .take_while(|&(start, _)| start < file.start)
+                .filter(|&(_, length)| length >= file.length);
+            choose(&mut spans)
+        };
+        if let Some((target_start, _)) = target {
+            move_file(disk, file.id, file.start, target_start, file.length);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_disk_map;
+
+    /// Compacts `disk_map` with the named strategy and renders the result.
+    fn compact_with(name: &str, disk_map: &str) -> String {
+        let mut disk = parse_disk_map(disk_map);
+        strategy(name).unwrap().compact(&mut disk);
+        disk.to_string()
+    }
+
+    #[test]
+    fn test_strategies_on_example() {
+        let example = "2333133121414131402";
+        assert_eq!(
+            compact_with("blocks", example),
+            "0099811188827773336446555566.............."
+        );
+        assert_eq!(
+            compact_with("first-fit", example),
+            "00992111777.44.333....5555.6666.....8888.."
+        );
+        assert_eq!(
+            compact_with("blocks-parallel", example),
+            compact_with("blocks", example)
+        );
+    }
+
+    #[test]
+    fn test_best_and_worst_fit() {
+        // 0...11.2: best fit saves the large gap for file 1
+        assert_eq!(compact_with("first-fit", "13211"), "0211....");
+        assert_eq!(compact_with("best-fit", "13211"), "011...2.");
+        assert_eq!(compact_with("worst-fit", "13211"), "0211....");
+
+        // 0.1...2: worst fit leaves the small gap for file 1
+        assert_eq!(compact_with("first-fit", "11131"), "021....");
+        assert_eq!(compact_with("best-fit", "11131"), "021....");
+        assert_eq!(compact_with("worst-fit", "11131"), "01.2...");
+    }
+
+    #[test]
+    fn test_split_once() {
+        // 0..111: file 1 fits nowhere whole, so its head fills the gap
+        // and its tail moves to the space the head left behind
+        assert_eq!(compact_with("first-fit", "123"), "0..111");
+        assert_eq!(compact_with("split-once", "123"), "0111..");
+
+        // 0.11222: the tail of file 2 has nowhere to go and stays put
+        assert_eq!(compact_with("split-once", "11203"), "0211.22");
+    }
+
+    #[test]
+    fn test_size_order() {
+        // 0..11.2: the larger file 1 moves before file 2 can take its gap
+        assert_eq!(compact_with("first-fit", "12211"), "02.11..");
+        assert_eq!(compact_with("size-order", "12211"), "0112...");
+    }
+
+    #[test]
+    fn test_pack_right() {
+        assert_eq!(
+            compact_with("pack-right", "2333133121414131402"),
+            "........111..00333.44.5555.6666.7772888899"
+        );
+        // 0..111....22222: file 0 fits in the rightmost gap, file 1 in none
+        assert_eq!(compact_with("pack-right", "12345"), "......111022222");
+        assert_eq!(
+            find_rightmost_free_span(&parse_disk_map("12345"), 3, 6),
+            Some(7)
+        );
+        assert_eq!(
+            find_rightmost_free_span(&parse_disk_map("12345"), 3, 7),
+            None
+        );
+    }
+
+    #[test]
+    fn test_spread_evenly() {
+        // 14 free blocks over 9 gaps: the first 5 get 2 blocks, the rest 1
+        assert_eq!(
+            compact_with("spread-evenly", "2333133121414131402"),
+            "00..111..2..333..44..5555.6666.777.8888.99"
+        );
+        assert_eq!(compact_with("spread-evenly", "1234500"), "0...111...22222");
+        assert_eq!(compact_with("spread-evenly", "11131"), "0..1..2");
+        // A lone file moves to the start
+        assert_eq!(compact_with("spread-evenly", "0331"), "111....");
+    }
+
+    #[test]
+    fn test_every_strategy_keeps_the_files() {
+        let disk = parse_disk_map("2333133121414131402");
+        let mut expected = disk.to_blocks();
+        expected.sort();
+        for strategy in STRATEGIES {
+            let mut compacted = disk.clone();
+            strategy.compact(&mut compacted);
+            let mut blocks = compacted.to_blocks();
+            blocks.sort();
+            assert_eq!(blocks, expected, "{}", strategy.name());
+        }
+    }
+}
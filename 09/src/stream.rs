@@ -0,0 +1,182 @@
+use std::io::{BufRead, ErrorKind};
+
+use aoc_core::{Error, ParseMode, Result};
+
+use crate::{Block, Disk};
+
+/// Reads a disk map incrementally, yielding one run of blocks per digit.
+///
+/// Only the reader's buffer of the map's text is held in memory; what is built
+/// from the runs is up to the caller. Whitespace anywhere in the map, including
+/// line breaks, is ignored. Any other non-digit is an error reporting its line,
+/// column and byte offset; in lenient mode it is reported on stderr and skipped
+/// instead.
+pub struct DiskMapReader<R> {
+    reader: R,
+    mode: ParseMode,
+    /// Byte offset of the next byte to read.
+    offset: u64,
+    line: usize,
+    column: usize,
+    next_file_id: usize,
+    is_file: bool,
+    failed: bool,
+}
+
+impl<R: BufRead> DiskMapReader<R> {
+    pub fn new(reader: R, mode: ParseMode) -> Self {
+        DiskMapReader {
+            reader,
+            mode,
+            offset: 0,
+            line: 1,
+            column: 1,
+            next_file_id: 0,
+            is_file: true,
+            failed: false,
+        }
+    }
+
+    /// Counts lines and byte offsets in errors as if the reader started at
+    /// `line` and byte `offset` of a larger input.
+    pub(crate) fn starting_at(mut self, line: usize, offset: u64) -> Self {
+        self.line = line;
+        self.offset = offset;
+        self
+    }
+
+    /// Consumes bytes up to and including the next digit.
+    fn next_digit(&mut self) -> Result<Option<u8>> {
+        loop {
+            let buffer = match self.reader.fill_buf() {
+                Ok([]) => return Ok(None),
+                Ok(buffer) => buffer,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(source) => return Err(Error::Io { path: None, source }),
+            };
+
+            let mut used = 0;
+            let mut digit = None;
+            for &byte in buffer {
+                let (line, column, offset) = (self.line, self.column, self.offset);
+                used += 1;
+                self.offset += 1;
+                if byte == b'\n' {
+                    self.line += 1;
+                    self.column = 1;
+                } else {
+                    self.column += 1;
+                }
+
+                match byte {
+                    b'0'..=b'9' => {
+                        digit = Some(byte - b'0');
+                        break;
+                    }
+                    _ if byte.is_ascii_whitespace() => {}
+                    _ => {
+                        let found = if byte.is_ascii() {
+                            format!("character {:?}", char::from(byte))
+                        } else {
+                            format!("byte 0x{:02x}", byte)
+                        };
+                        let error = Error::parse(
+                            line,
+                            column,
+                            format!("invalid {} at byte offset {}", found, offset),
+                        );
+                        self.mode.skip_or_fail(error)?;
+                    }
+                }
+            }
+            self.reader.consume(used);
+            if digit.is_some() {
+                return Ok(digit);
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for DiskMapReader<R> {
+    /// A run of `length` blocks, all of one file or all free.
+    type Item = Result<(Block, usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let digit = match self.next_digit() {
+            Ok(digit) => digit?,
+            Err(e) => {
+                self.failed = true;
+                return Some(Err(e));
+            }
+        };
+
+        let block = if self.is_file {
+            self.next_file_id += 1;
+            Some(self.next_file_id - 1)
+        } else {
+            None
+        };
+        self.is_file = !self.is_file;
+        Some(Ok((block, usize::from(digit))))
+    }
+}
+
+/// Builds a disk from a disk map read incrementally from `reader`.
+pub fn read_disk<R: BufRead>(reader: R, mode: ParseMode) -> Result<Disk> {
+    let mut disk = Disk::default();
+    for run in DiskMapReader::new(reader, mode) {
+        let (block, length) = run?;
+        disk.push(block, length);
+    }
+    Ok(disk)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::parse_disk_map;
+
+    #[test]
+    fn test_read_disk_streams_whitespace_separated_maps() {
+        let disk = read_disk("2333133121\n4141 3140\t2\n".as_bytes(), ParseMode::Strict);
+        assert_eq!(disk.unwrap(), parse_disk_map("2333133121414131402"));
+
+        // A reader with a tiny buffer sees the map a byte at a time
+        let reader = BufReader::with_capacity(1, "1 2\n3 4 5".as_bytes());
+        assert_eq!(
+            read_disk(reader, ParseMode::Strict).unwrap(),
+            parse_disk_map("12345")
+        );
+    }
+
+    #[test]
+    fn test_read_disk_reports_invalid_characters() {
+        let err = read_disk("123\n45x6".as_bytes(), ParseMode::Strict).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: invalid character 'x' at byte offset 6"
+        );
+
+        let err = read_disk("12\u{e9}".as_bytes(), ParseMode::Strict).unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("invalid byte 0xc3 at byte offset 2"));
+
+        // Lenient mode skips them
+        let disk = read_disk("1x2345".as_bytes(), ParseMode::Lenient).unwrap();
+        assert_eq!(disk, parse_disk_map("12345"));
+    }
+
+    #[test]
+    fn test_disk_map_reader_yields_runs() {
+        let runs: Vec<_> = DiskMapReader::new("12 30".as_bytes(), ParseMode::Strict)
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(runs, vec![(Some(0), 1), (None, 2), (Some(1), 3), (None, 0)]);
+    }
+}
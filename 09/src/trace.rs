@@ -0,0 +1,262 @@
+use std::io::{self, Write};
+
+use aoc_core::{Error, Result};
+
+use crate::{move_file, Disk};
+
+/// One call to [`move_file`](crate::move_file): `length` blocks of `file_id` moved
+/// from position `from` to position `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub file_id: usize,
+    pub from: usize,
+    pub to: usize,
+    pub length: usize,
+    /// The disk checksum once the move is made, if it was recorded.
+    pub checksum: Option<u128>,
+}
+
+impl Move {
+    /// The move as a single-line JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"file_id\":{},\"from\":{},\"to\":{},\"length\":{}{}}}",
+            self.file_id,
+            self.from,
+            self.to,
+            self.length,
+            self.checksum.map_or(String::new(), |checksum| format!(
+                ",\"checksum\":{}",
+                checksum
+            ))
+        )
+    }
+
+    /// Parses a line written by [`Move::to_json`]. The fields may come in any
+    /// order, and `checksum` may be left out.
+    fn from_json(line: &str) -> std::result::Result<Self, String> {
+        let body = line
+            .trim()
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .ok_or("expected a JSON object")?;
+
+        let (mut file_id, mut from, mut to, mut length) = (None, None, None, None);
+        let mut checksum = None;
+        for field in body.split(',') {
+            let (key, value) = field
+                .split_once(':')
+                .ok_or_else(|| format!("expected \"key\":value, found '{}'", field.trim()))?;
+            let invalid = || format!("invalid number '{}'", value.trim());
+            let value: u128 = value.trim().parse().map_err(|_| invalid())?;
+            if key.trim() == "\"checksum\"" {
+                checksum = Some(value);
+                continue;
+            }
+            let value = usize::try_from(value).map_err(|_| invalid())?;
+            let slot = match key.trim() {
+                "\"file_id\"" => &mut file_id,
+                "\"from\"" => &mut from,
+                "\"to\"" => &mut to,
+                "\"length\"" => &mut length,
+                key => return Err(format!("unknown field {}", key)),
+            };
+            *slot = Some(value);
+        }
+
+        let missing = |name: &str| format!("missing field \"{}\"", name);
+        Ok(Move {
+            file_id: file_id.ok_or_else(|| missing("file_id"))?,
+            from: from.ok_or_else(|| missing("from"))?,
+            to: to.ok_or_else(|| missing("to"))?,
+            length: length.ok_or_else(|| missing("length"))?,
+            checksum,
+        })
+    }
+}
+
+/// Writes one JSON object per move, one per line.
+pub fn write_trace<W: Write>(mut writer: W, moves: &[Move]) -> io::Result<()> {
+    for m in moves {
+        writeln!(writer, "{}", m.to_json())?;
+    }
+    writer.flush()
+}
+
+/// Reads a trace written by [`write_trace`], skipping blank lines.
+pub fn parse_trace(input: &str) -> Result<Vec<Move>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_num, line)| {
+            Move::from_json(line).map_err(|message| Error::parse(line_num + 1, 1, message))
+        })
+        .collect()
+}
+
+/// Applies `moves` to `disk` in order. Unlike [`move_file`], an impossible move is
+/// reported as an error naming its position in the trace instead of panicking,
+/// as is a checksum that differs from the one recorded with the move.
+pub fn replay(disk: &mut Disk, moves: &[Move]) -> Result<()> {
+    for (step, m) in moves.iter().enumerate() {
+        let mismatch = |message| Error::Mismatch(format!("move {}: {}", step + 1, message));
+        disk.check_move(m.file_id, m.from, m.to, m.length)
+            .map_err(mismatch)?;
+        move_file(disk, m.file_id, m.from, m.to, m.length);
+        if let Some(checksum) = m.checksum.filter(|&checksum| checksum != disk.checksum()) {
+            return Err(mismatch(format!(
+                "the checksum is {}, but the trace has {}",
+                disk.checksum(),
+                checksum
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Replays `moves` on a copy of `initial` and checks that the result is `expected`.
+pub fn verify_replay(initial: &Disk, moves: &[Move], expected: &Disk) -> Result<()> {
+    let mut disk = initial.clone();
+    replay(&mut disk, moves)?;
+    if disk == *expected {
+        return Ok(());
+    }
+
+    let (replayed, expected) = (disk.to_blocks(), expected.to_blocks());
+    let position = replayed
+        .iter()
+        .zip(&expected)
+        .position(|(a, b)| a != b)
+        .unwrap_or(replayed.len().min(expected.len()));
+    Err(Error::Mismatch(format!(
+        "replayed disk differs from the expected one at block {}",
+        position
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compact_part_one, compact_part_two, parse_disk_map};
+
+    #[test]
+    fn test_trace_records_every_move() {
+        // 0..111....22222: file 2 fills the first gap from its tail, then the second
+        let mut disk = parse_disk_map("12345");
+        disk.record_moves();
+        compact_part_one(&mut disk);
+        let moves = disk.take_moves();
+
+        assert_eq!(
+            moves,
+            vec![
+                Move {
+                    file_id: 2,
+                    from: 13,
+                    to: 1,
+                    length: 2,
+                    checksum: Some(84)
+                },
+                Move {
+                    file_id: 2,
+                    from: 10,
+                    to: 6,
+                    length: 3,
+                    checksum: Some(60)
+                },
+            ]
+        );
+        assert!(disk.take_moves().is_empty());
+    }
+
+    #[test]
+    fn test_trace_round_trip_and_replay() {
+        let initial = parse_disk_map("2333133121414131402");
+        let mut compacted = initial.clone();
+        compacted.record_moves();
+        compact_part_two(&mut compacted);
+        let moves = compacted.take_moves();
+        assert_eq!(moves.len(), 4);
+
+        let mut jsonl = Vec::new();
+        write_trace(&mut jsonl, &moves).unwrap();
+        let jsonl = String::from_utf8(jsonl).unwrap();
+        assert_eq!(
+            jsonl.lines().next(),
+            Some(r#"{"file_id":9,"from":40,"to":2,"length":2,"checksum":3432}"#)
+        );
+        let parsed = parse_trace(&jsonl).unwrap();
+        assert_eq!(parsed, moves);
+
+        // Traces without checksums still replay
+        let old = r#"{"file_id":9,"from":40,"to":2,"length":2}"#;
+        let parsed_old = parse_trace(old).unwrap();
+        assert_eq!(parsed_old[0].checksum, None);
+        replay(&mut initial.clone(), &parsed_old).unwrap();
+
+        // A wrong checksum is caught at the move it belongs to
+        let mut tampered = moves.clone();
+        tampered[1].checksum = Some(1);
+        let err = replay(&mut initial.clone(), &tampered).unwrap_err();
+        assert!(err.to_string().starts_with("move 2: the checksum is "));
+
+        // Empty moves and moves past the largest position are errors, not panics
+        let empty = parse_trace(r#"{"file_id":0,"from":0,"to":5,"length":0}"#).unwrap();
+        let err = replay(&mut initial.clone(), &empty).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "move 1: a move must cover at least one block"
+        );
+        let line = format!(
+            r#"{{"file_id":0,"from":{},"to":1,"length":2}}"#,
+            usize::MAX - 1
+        );
+        let overflowing = parse_trace(&line).unwrap();
+        let err = replay(&mut initial.clone(), &overflowing).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "move 1: blocks {}.. run past the largest position",
+                usize::MAX - 1
+            )
+        );
+
+        verify_replay(&initial, &parsed, &compacted).unwrap();
+        // Dropping a move leaves a different disk
+        let err = verify_replay(&initial, &parsed[1..], &compacted).unwrap_err();
+        assert!(matches!(err, Error::Mismatch(_)));
+    }
+
+    #[test]
+    fn test_replay_rejects_impossible_moves() {
+        let initial = parse_disk_map("12345");
+        let moves = [Move {
+            file_id: 1,
+            from: 3,
+            to: 9,
+            length: 3,
+            checksum: None,
+        }];
+        let err = replay(&mut initial.clone(), &moves).unwrap_err();
+        assert_eq!(err.to_string(), "move 1: blocks 9..12 are not free");
+
+        // Overlapping the blocks being moved is fine: shift file 1 right by one
+        let moves = [Move {
+            file_id: 1,
+            from: 3,
+            to: 4,
+            length: 3,
+            checksum: None,
+        }];
+        let mut disk = initial.clone();
+        replay(&mut disk, &moves).unwrap();
+        assert_eq!(
+            disk.to_blocks()[3..8],
+            [None, Some(1), Some(1), Some(1), None]
+        );
+
+        let err = parse_trace("{\"file_id\":1,\"from\":3}\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: missing field \"to\"");
+    }
+}
@@ -0,0 +1,190 @@
+use std::io::BufRead;
+
+use aoc_core::{Error, ParseMode, Result};
+
+use crate::{move_file, CompactionStrategy, Disk, DiskMapReader, FreeIndex};
+
+/// Several disks making up one volume, each a partition with its own blocks.
+///
+/// File ids run on from one partition to the next, so they stay unique across
+/// the volume and files can move between partitions. Checksums are per
+/// partition, counting positions from the start of the partition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Volume {
+    pub partitions: Vec<Disk>,
+}
+
+impl Volume {
+    /// The checksum of each partition.
+    pub fn checksums(&self) -> Vec<u128> {
+        self.partitions.iter().map(Disk::checksum).collect()
+    }
+
+    /// The sum of the partition checksums.
+    pub fn checksum(&self) -> u128 {
+        self.partitions.iter().map(Disk::checksum).sum()
+    }
+
+    /// Compacts each partition on its own, leaving every file where it was.
+    pub fn compact_partitions(&mut self, strategy: &dyn CompactionStrategy) {
+        for disk in &mut self.partitions {
+            strategy.compact(disk);
+        }
+    }
+
+    /// Part Two across the whole volume: moves whole files, highest id first,
+    /// to the leftmost span that fits them in an earlier partition, or failing
+    /// that, before them in their own.
+    pub fn compact_across_partitions(&mut self) {
+        let mut files: Vec<_> = self
+            .partitions
+            .iter()
+            .enumerate()
+            .flat_map(|(partition, disk)| disk.extents().map(move |file| (partition, file.clone())))
+            .collect();
+        files.sort_by_key(|(_, file)| std::cmp::Reverse(file.id));
+
+        let mut indexes: Vec<_> = self.partitions.iter().map(FreeIndex::new).collect();
+        for (partition, file) in files {
+            let earlier = (0..partition).find_map(|target| {
+                indexes[target]
+                    .find(&self.partitions[target], file.length, usize::MAX)
+                    .map(|start| (target, start))
+            });
+            let target = earlier.or_else(|| {
+                indexes[partition]
+                    .find(&self.partitions[partition], file.length, file.start)
+                    .map(|start| (partition, start))
+            });
+            let Some((target, target_start)) = target else {
+                continue;
+            };
+
+            if target == partition {
+                move_file(
+                    &mut self.partitions[partition],
+                    file.id,
+                    file.start,
+                    target_start,
+                    file.length,
+                );
+            } else {
+                let source = &mut self.partitions[partition];
+                source.take_extent(file.id, file.start, file.length);
+                source.insert_free(file.start, file.length);
+                let destination = &mut self.partitions[target];
+                destination.take_free(target_start, file.length);
+                destination.insert_extent(file.id, target_start, file.length);
+            }
+
+            // Index what is left of the target span and the span the file left behind
+            for (index, pos) in [
+                (target, target_start + file.length),
+                (partition, file.start),
+            ] {
+                if let Some((start, length)) = self.partitions[index].free_span_at(pos) {
+                    indexes[index].insert(start, length);
+                }
+            }
+        }
+    }
+}
+
+/// Reads a volume with one partition per line of `reader`, skipping blank lines.
+/// Each line is a disk map as [`read_disk`](crate::read_disk) reads it, except
+/// that it ends at the line break.
+pub fn read_volume<R: BufRead>(mut reader: R, mode: ParseMode) -> Result<Volume> {
+    let mut volume = Volume::default();
+    let (mut line_num, mut offset) = (1, 0);
+    let mut next_id = 0;
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .map_err(|source| Error::Io { path: None, source })?;
+        if read == 0 {
+            return Ok(volume);
+        }
+
+        let mut disk = Disk::default();
+        let mut files = 0;
+        let runs = DiskMapReader::new(line.as_slice(), mode).starting_at(line_num, offset);
+        for run in runs {
+            let (block, length) = run?;
+            if block.is_some() {
+                files += 1;
+            }
+            disk.push(block.map(|id| next_id + id), length);
+        }
+        if files > 0 {
+            next_id += files;
+            volume.partitions.push(disk);
+        }
+        line_num += 1;
+        offset += read as u64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_checksum, FirstFit};
+
+    #[test]
+    fn test_read_volume() {
+        let volume = read_volume(
+            "12345\n\n2333133121414131402\n".as_bytes(),
+            ParseMode::Strict,
+        );
+        let volume = volume.unwrap();
+        assert_eq!(volume.partitions.len(), 2);
+        assert_eq!(volume.partitions[0].to_string(), "0..111....22222");
+        // Ids carry on from the first partition: 3 to 9, then a to c
+        assert_eq!(
+            volume.partitions[1].to_string(),
+            "33...444...5...666.77.8888.9999.aaa.bbbbcc"
+        );
+        assert_eq!(
+            volume.checksums(),
+            vec![132, calculate_checksum(&volume.partitions[1])]
+        );
+        assert_eq!(volume.checksum(), volume.checksums().iter().sum());
+
+        // Errors point into the whole input
+        let err = read_volume("12\n3x".as_bytes(), ParseMode::Strict).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: invalid character 'x' at byte offset 4"
+        );
+    }
+
+    #[test]
+    fn test_compact_volume() {
+        let input = "12345\n2333133121414131402\n";
+        let mut separate = read_volume(input.as_bytes(), ParseMode::Strict).unwrap();
+        separate.compact_partitions(&FirstFit);
+        assert_eq!(separate.partitions[0].to_string(), "0..111....22222");
+        assert_eq!(
+            separate.partitions[1].to_string(),
+            "33cc5444aaa.77.666....8888.9999.....bbbb.."
+        );
+
+        // Across partitions, the later files fill the gaps of the first partition
+        let mut across = read_volume(input.as_bytes(), ParseMode::Strict).unwrap();
+        across.compact_across_partitions();
+        assert_eq!(across.partitions[0].to_string(), "0cc111bbbb22222");
+        assert_eq!(
+            across.partitions[1].to_string(),
+            "33aaa444775.666.......8888.9999..........."
+        );
+        assert!(across.checksum() < separate.checksum());
+
+        let blocks = |volume: &Volume| {
+            let mut blocks: Vec<_> = volume.partitions.iter().flat_map(Disk::to_blocks).collect();
+            blocks.sort();
+            blocks
+        };
+        assert_eq!(blocks(&across), blocks(&separate));
+    }
+}
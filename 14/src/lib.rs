@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+
+use aoc_core::{Answer, Error, ParseMode, Result, Solution};
+
+/// Path of the puzzle input shipped alongside this crate.
+pub const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day_14.in");
+
+/// Width of the room in the puzzle.
+pub const WIDTH: i64 = 101;
+
+/// Height of the room in the puzzle.
+pub const HEIGHT: i64 = 103;
+
+/// Struct representing a robot with position (x, y) and velocity (vx, vy)
+#[derive(Debug, Clone)]
+pub struct Robot {
+    pub x: i64,
+    pub y: i64,
+    pub vx: i64,
+    pub vy: i64,
+}
+
+/// Helper function to perform Euclidean modulo operation
+/// Equivalent to `rem_euclid` for compatibility with older Rust versions
+pub fn rem_euclid(a: i64, b: i64) -> i64 {
+    ((a % b) + b) % b
+}
+
+/// Parses an `a,b` pair of integers following `prefix` (e.g. `p=` or `v=`).
+/// `column` is the 0-based offset of `token` within its line, used for error positions.
+fn parse_pair(line_num: usize, column: usize, token: &str, prefix: &str) -> Result<(i64, i64)> {
+    let body = token
+        .strip_prefix(prefix)
+        .ok_or_else(|| Error::parse(line_num, column + 1, format!("expected '{}'", prefix)))?;
+    let column = column + prefix.len();
+
+    let (a, b) = body.split_once(',').ok_or_else(|| {
+        Error::parse(line_num, column + 1, "expected two comma-separated numbers")
+    })?;
+    let number = |text: &str, column: usize| {
+        text.parse::<i64>()
+            .map_err(|_| Error::parse(line_num, column + 1, format!("invalid number {:?}", text)))
+    };
+
+    Ok((number(a, column)?, number(b, column + a.len() + 1)?))
+}
+
+/// Function to parse a single robot line.
+/// Expected format: p=x,y v=dx,dy
+/// Example: p=0,4 v=3,-3
+pub fn parse_robot(line_num: usize, line: &str) -> Result<Robot> {
+    // Split into whitespace-separated fields, remembering where each starts
+    let mut fields = Vec::new();
+    let mut offset = 0;
+    for field in line.split_whitespace() {
+        let column = offset + line[offset..].find(field).unwrap_or(0);
+        fields.push((column, field));
+        offset = column + field.len();
+    }
+
+    let [(pos_column, pos_part), (vel_column, vel_part)] = fields[..] else {
+        return Err(Error::parse(line_num, 1, "expected 'p=x,y v=dx,dy'"));
+    };
+
+    // Parse position and velocity
+    let (x, y) = parse_pair(line_num, pos_column, pos_part, "p=")?;
+    let (vx, vy) = parse_pair(line_num, vel_column, vel_part, "v=")?;
+
+    Ok(Robot { x, y, vx, vy })
+}
+
+/// Function to parse robots from the input, one robot per line.
+/// Blank lines are ignored; malformed lines are skipped or rejected depending on `mode`.
+pub fn parse_robots(input: &str, mode: ParseMode) -> Result<Vec<Robot>> {
+    let mut robots = Vec::new();
+
+    for (line_num, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_robot(line_num + 1, line) {
+            Ok(robot) => robots.push(robot),
+            Err(e) => mode.skip_or_fail(e)?,
+        }
+    }
+
+    Ok(robots)
+}
+
+/// Function to compute the safety factor after `t` seconds (Part One)
+pub fn compute_safety_factor(robots: &[Robot], width: i64, height: i64, t: i64) -> i64 {
+    let mut q1 = 0;
+    let mut q2 = 0;
+    let mut q3 = 0;
+    let mut q4 = 0;
+
+    for robot in robots {
+        // Calculate position at time t with wrapping
+        let x = rem_euclid(robot.x + robot.vx * t, width);
+        let y = rem_euclid(robot.y + robot.vy * t, height);
+
+        // Determine quadrants based on x and y
+        if x < width / 2 {
+            if y < height / 2 {
+                q1 += 1;
+            }
+            if y > height / 2 {
+                q2 += 1;
+            }
+        }
+        if x > width / 2 {
+            if y < height / 2 {
+                q3 += 1;
+            }
+            if y > height / 2 {
+                q4 += 1;
+            }
+        }
+    }
+
+    q1 * q2 * q3 * q4
+}
+
+/// Function to find the fewest number of seconds for unique alignment (Part Two)
+pub fn find_alignment_time(robots: &[Robot], width: i64, height: i64) -> Option<i64> {
+    let max_time = width * height; // Positions repeat after width * height seconds
+
+    for t in 0..=max_time {
+        let mut positions = HashSet::new();
+        let mut all_unique = true;
+
+        for robot in robots {
+            let x = rem_euclid(robot.x + robot.vx * t, width);
+            let y = rem_euclid(robot.y + robot.vy * t, height);
+
+            // If the position is already in the set, not unique
+            if !positions.insert((x, y)) {
+                all_unique = false;
+                break;
+            }
+        }
+
+        if all_unique {
+            return Some(t);
+        }
+    }
+
+    None
+}
+
+/// Day 14: Restroom Redoubt.
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Vec<Robot>;
+
+    fn parse(input: &str, mode: ParseMode) -> Result<Self::Input> {
+        parse_robots(input, mode)
+    }
+
+    /// Safety factor after 100 seconds.
+    fn part1(robots: &Self::Input) -> Result<Answer> {
+        Ok(compute_safety_factor(robots, WIDTH, HEIGHT, 100).into())
+    }
+
+    /// Fewest seconds until no two robots share a tile.
+    fn part2(robots: &Self::Input) -> Result<Answer> {
+        find_alignment_time(robots, WIDTH, HEIGHT)
+            .map(Answer::from)
+            .ok_or_else(|| Error::no_answer("No alignment found within the time frame."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.in");
+
+    // The example room is 11 tiles wide and 7 tall
+    const EXAMPLE_WIDTH: i64 = 11;
+    const EXAMPLE_HEIGHT: i64 = 7;
+
+    #[test]
+    fn test_parse_robot() {
+        let robot = parse_robot(1, "p=0,4 v=3,-3").unwrap();
+        assert_eq!((robot.x, robot.y, robot.vx, robot.vy), (0, 4, 3, -3));
+    }
+
+    #[test]
+    fn test_example_safety_factor() {
+        let robots = parse_robots(EXAMPLE, ParseMode::Strict).unwrap();
+        assert_eq!(robots.len(), 12);
+        assert_eq!(
+            compute_safety_factor(&robots, EXAMPLE_WIDTH, EXAMPLE_HEIGHT, 100),
+            12
+        );
+    }
+
+    #[test]
+    fn test_single_robot_wraps() {
+        // The puzzle follows the robot at p=2,4 v=2,-3 to 1,3 after 5 seconds
+        let robot = parse_robot(1, "p=2,4 v=2,-3").unwrap();
+        let x = rem_euclid(robot.x + robot.vx * 5, EXAMPLE_WIDTH);
+        let y = rem_euclid(robot.y + robot.vy * 5, EXAMPLE_HEIGHT);
+        assert_eq!((x, y), (1, 3));
+    }
+
+    #[test]
+    fn test_example_alignment_time() {
+        let robots = parse_robots(EXAMPLE, ParseMode::Strict).unwrap();
+        let t = find_alignment_time(&robots, EXAMPLE_WIDTH, EXAMPLE_HEIGHT).unwrap();
+        assert!(t < EXAMPLE_WIDTH * EXAMPLE_HEIGHT);
+
+        let positions: HashSet<_> = robots
+            .iter()
+            .map(|robot| {
+                (
+                    rem_euclid(robot.x + robot.vx * t, EXAMPLE_WIDTH),
+                    rem_euclid(robot.y + robot.vy * t, EXAMPLE_HEIGHT),
+                )
+            })
+            .collect();
+        assert_eq!(positions.len(), robots.len());
+    }
+}
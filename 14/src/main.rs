@@ -0,0 +1,35 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc_core::cli::{self, Args};
+use aoc_core::{Error, Format, ParseMode, Result, Solution};
+use day14::{Day14, INPUT_PATH};
+
+fn run(mut args: Args) -> Result<()> {
+    let format: Format = args.parsed("--format")?.unwrap_or_default();
+    args.finish()?;
+
+    // Read and parse robots from the input file
+    let input = aoc_core::read_input(Some(Path::new(INPUT_PATH)))?;
+    if format == Format::Json {
+        return aoc_core::print_json::<Day14>(&input, ParseMode::Strict);
+    }
+    let robots = Day14::parse(&input, ParseMode::Strict)?;
+
+    // Part One: Compute safety factor after 100 seconds
+    let safety_factor = Day14::part1(&robots)?;
+    println!("Safety Factor after 100 seconds: {}", safety_factor);
+
+    // Part Two: Find the fewest number of seconds for unique alignment
+    match Day14::part2(&robots) {
+        Ok(t) => println!("Fewest number of seconds for alignment (Part Two): {}", t),
+        Err(Error::NoAnswer(message)) => println!("{}", message),
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    cli::report(run(Args::from_env()))
+}
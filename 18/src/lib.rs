@@ -0,0 +1,148 @@
+use std::path::Path;
+
+use aoc_core::{Answer, Error, ParseMode, Result, Solution};
+use aoc_grid::{Grid, Point};
+
+/// Path of the puzzle input shipped alongside this crate.
+pub const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day_18.in");
+
+/// Size (width, height) of the memory space in the puzzle.
+pub const GRID_SIZE: (usize, usize) = (71, 71);
+
+/// Reads byte positions from a file.
+/// Each line should be in the format: x,y
+pub fn read_byte_positions<P: AsRef<Path>>(filename: P, mode: ParseMode) -> Result<Vec<Point>> {
+    let input = aoc_core::read_input(Some(filename.as_ref()))?;
+    parse_byte_positions(&input, mode)
+}
+
+/// Parses byte positions, one `x,y` pair per line.
+/// Blank lines are ignored; malformed lines are skipped or rejected depending on `mode`.
+pub fn parse_byte_positions(input: &str, mode: ParseMode) -> Result<Vec<Point>> {
+    let mut byte_positions = Vec::new();
+
+    for (line_num, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue; // Skip empty lines
+        }
+        let indent = line.len() - line.trim_start().len();
+        let Some((x, y)) = line.trim().split_once(',') else {
+            let error = Error::parse(
+                line_num + 1,
+                indent + 1,
+                format!("invalid format: {}", line),
+            );
+            mode.skip_or_fail(error)?;
+            continue;
+        };
+        match (x.parse::<usize>(), y.parse::<usize>()) {
+            (Ok(x), Ok(y)) => byte_positions.push(Point::new(x, y)),
+            (Err(_), _) => {
+                let error =
+                    Error::parse(line_num + 1, indent + 1, format!("invalid number {:?}", x));
+                mode.skip_or_fail(error)?;
+            }
+            (_, Err(_)) => {
+                let column = indent + x.len() + 2;
+                let error = Error::parse(line_num + 1, column, format!("invalid number {:?}", y));
+                mode.skip_or_fail(error)?;
+            }
+        }
+    }
+
+    Ok(byte_positions)
+}
+
+/// Performs BFS over the grid, where '.' is safe and '#' is corrupted,
+/// to check if a path exists from start to end.
+/// Returns true if no path exists (blocked), false otherwise.
+pub fn is_path_blocked(grid: &Grid<char>, start: Point, end: Point) -> bool {
+    if !grid.contains(start) || !grid.contains(end) {
+        return true;
+    }
+
+    let distances = grid.bfs(start, |&c| c == '.');
+    distances[end].is_none()
+}
+
+/// Finds the first byte that blocks the path.
+/// Returns Some(position) if a blocking byte is found, None otherwise.
+pub fn find_blocking_byte(byte_positions: &[Point], grid_size: (usize, usize)) -> Option<Point> {
+    let (width, height) = grid_size;
+    let mut grid = Grid::new(width, height, '.');
+    let start = Point::new(0, 0);
+    let end = Point::new(width - 1, height - 1);
+
+    for &byte in byte_positions {
+        // Skip corrupting start or end positions
+        if byte == start || byte == end {
+            continue;
+        }
+
+        match grid.get_mut(byte) {
+            Some(cell) => *cell = '#', // Corrupt the cell
+            None => {
+                eprintln!("Byte position out of bounds: {}", byte);
+                continue;
+            }
+        }
+
+        // Check if the path is blocked after this corruption
+        if is_path_blocked(&grid, start, end) {
+            return Some(byte); // Found the blocking byte
+        }
+    }
+
+    None // Path remains open after all bytes
+}
+
+/// Day 18: RAM Run.
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Vec<Point>;
+
+    fn parse(input: &str, mode: ParseMode) -> Result<Self::Input> {
+        parse_byte_positions(input, mode)
+    }
+
+    /// Part One is only solved in `18/part_1.php`.
+    fn part1(_: &Self::Input) -> Result<Answer> {
+        Err(Error::no_answer(
+            "Part One is not solved in Rust; see 18/part_1.php.",
+        ))
+    }
+
+    /// Coordinates of the first byte that cuts off the exit.
+    fn part2(byte_positions: &Self::Input) -> Result<Answer> {
+        find_blocking_byte(byte_positions, GRID_SIZE)
+            .map(|byte| byte.to_string().into())
+            .ok_or_else(|| Error::no_answer("Path remains open after all bytes."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.in");
+
+    // The example memory space is 7x7
+    const EXAMPLE_GRID_SIZE: (usize, usize) = (7, 7);
+
+    #[test]
+    fn test_parse_example() {
+        let bytes = parse_byte_positions(EXAMPLE, ParseMode::Strict).unwrap();
+        assert_eq!(bytes.len(), 25);
+        assert_eq!(bytes[0], Point::new(5, 4));
+    }
+
+    #[test]
+    fn test_example_blocking_byte() {
+        let bytes = parse_byte_positions(EXAMPLE, ParseMode::Strict).unwrap();
+        let byte = find_blocking_byte(&bytes, EXAMPLE_GRID_SIZE).unwrap();
+        assert_eq!(byte.to_string(), "6,1");
+    }
+}
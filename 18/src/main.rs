@@ -0,0 +1,31 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc_core::cli::{self, Args};
+use aoc_core::{Error, Format, ParseMode, Result, Solution};
+use day18::{read_byte_positions, Day18, INPUT_PATH};
+
+fn run(mut args: Args) -> Result<()> {
+    let format: Format = args.parsed("--format")?.unwrap_or_default();
+    args.finish()?;
+
+    if format == Format::Json {
+        let input = aoc_core::read_input(Some(Path::new(INPUT_PATH)))?;
+        return aoc_core::print_json::<Day18>(&input, ParseMode::Strict);
+    }
+
+    let byte_positions = read_byte_positions(INPUT_PATH, ParseMode::Strict)?;
+
+    // Grid size is 71x71
+    match Day18::part2(&byte_positions) {
+        Ok(byte) => println!("{}", byte),
+        Err(Error::NoAnswer(message)) => println!("{}", message),
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    cli::report(run(Args::from_env()))
+}
@@ -0,0 +1,205 @@
+use std::collections::{HashSet, VecDeque};
+
+use aoc_core::{Answer, Error, ParseMode, Result, Solution};
+use aoc_grid::{Grid, Point};
+
+/// Path of the puzzle input shipped alongside this crate.
+pub const INPUT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/day_20.in");
+
+/// Minimum number of picoseconds a cheat must save to be counted.
+pub const MIN_SAVING: usize = 100;
+
+// Function to read and parse the grid
+// Only '#', '.', 'S' and 'E' are expected, with exactly one start and one end;
+// anything else is skipped or rejected depending on `mode`.
+pub fn parse_grid(input: &str, mode: ParseMode) -> Result<(Grid<char>, Point, Point)> {
+    let mut start = None;
+    let mut end = None;
+    let mut problems = Vec::new();
+
+    let grid = Grid::parse_with(input, |p, c| match c {
+        'S' | 'E' => {
+            let marker = if c == 'S' { &mut start } else { &mut end };
+            if marker.replace(p).is_some() {
+                problems.push(Error::parse(p.y + 1, p.x + 1, format!("duplicate '{}'", c)));
+            }
+            '.' // Treat 'S' and 'E' as track
+        }
+        '#' | '.' => c,
+        _ => {
+            problems.push(Error::parse(
+                p.y + 1,
+                p.x + 1,
+                format!("unexpected character {:?}", c),
+            ));
+            '#' // Skipped cells are walls, so they never open up the track
+        }
+    })?;
+
+    for problem in problems {
+        mode.skip_or_fail(problem)?;
+    }
+
+    let below_grid = grid.height() + 1;
+    let start = start.ok_or_else(|| Error::parse(below_grid, 1, "missing start 'S'"))?;
+    let end = end.ok_or_else(|| Error::parse(below_grid, 1, "missing end 'E'"))?;
+
+    Ok((grid, start, end))
+}
+
+// BFS to find distances from a given start position
+pub fn bfs(grid: &Grid<char>, start: Point) -> Grid<Option<usize>> {
+    grid.bfs(start, |&c| c != '#')
+}
+
+/// The race track together with the BFS distances from both of its ends.
+#[derive(Debug, Clone)]
+pub struct RaceTrack {
+    pub grid: Grid<char>,
+    pub distance_start: Grid<Option<usize>>,
+    pub distance_end: Grid<Option<usize>>,
+    /// Length of the shortest path from start to end without cheating.
+    pub shortest_path_length: usize,
+}
+
+impl RaceTrack {
+    /// Runs the BFS from both ends of the track.
+    /// Returns None if the end cannot be reached without cheating.
+    pub fn new(grid: Grid<char>, start: Point, end: Point) -> Option<Self> {
+        let distance_start = bfs(&grid, start);
+        let distance_end = bfs(&grid, end);
+        let shortest_path_length = distance_start[end]?;
+
+        Some(RaceTrack {
+            grid,
+            distance_start,
+            distance_end,
+            shortest_path_length,
+        })
+    }
+}
+
+/// Counts the distinct cheats of up to 2 picoseconds that save at least `min_saving`.
+pub fn count_cheats(track: &RaceTrack, min_saving: usize) -> usize {
+    let grid = &track.grid;
+    let mut valid_cheats: HashSet<(Point, Point)> = HashSet::new();
+
+    // Iterate through all positions on the grid
+    for p1 in grid.points() {
+        // Check if p1 is on the shortest path
+        if let (Some(d_start_p1), Some(d_end_p1)) =
+            (track.distance_start[p1], track.distance_end[p1])
+        {
+            if d_start_p1 + d_end_p1 == track.shortest_path_length {
+                // Find all p2 reachable from p1 in up to 2 steps, allowing to pass through walls
+                let mut p2_set: HashSet<Point> = HashSet::new();
+                let mut p2_queue: VecDeque<(Point, usize)> = VecDeque::new();
+                p2_queue.push_back((p1, 0));
+                p2_set.insert(p1);
+
+                while let Some((current_p2, steps)) = p2_queue.pop_front() {
+                    if steps >= 2 {
+                        continue;
+                    }
+
+                    // Allow passing through walls during cheat steps
+                    // But p2 must end on track
+                    for new_p2 in grid.neighbours4(current_p2) {
+                        if p2_set.insert(new_p2) {
+                            p2_queue.push_back((new_p2, steps + 1));
+                        }
+                    }
+                }
+
+                // After BFS, keep only positions on track
+                for p2 in p2_set.into_iter().filter(|&p2| grid[p2] != '#') {
+                    // Calculate time_saved
+                    // time_saved = shortest_path_length - (distance_start[p1] + distance_end[p2] + 2)
+                    // Ensure p2 is reachable from start and end
+                    if let Some(d_end_p2) = track.distance_end[p2] {
+                        let time_saved = track.shortest_path_length as isize
+                            - ((d_start_p1 as isize) + (d_end_p2 as isize) + 2);
+                        if time_saved >= min_saving as isize {
+                            valid_cheats.insert((p1, p2));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    valid_cheats.len()
+}
+
+/// Day 20: Race Condition.
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = RaceTrack;
+
+    fn parse(input: &str, mode: ParseMode) -> Result<Self::Input> {
+        let (grid, start, end) = parse_grid(input, mode)?;
+        RaceTrack::new(grid, start, end)
+            .ok_or_else(|| Error::no_answer("No path found from Start to End without cheating."))
+    }
+
+    /// Number of cheats of up to 2 picoseconds saving at least 100 picoseconds.
+    fn part1(track: &Self::Input) -> Result<Answer> {
+        Ok(count_cheats(track, MIN_SAVING).into())
+    }
+
+    /// Part Two is only solved in `20/part_2/main.go`.
+    fn part2(_: &Self::Input) -> Result<Answer> {
+        Err(Error::no_answer(
+            "Part Two is not solved in Rust; see 20/part_2/main.go.",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.in");
+
+    fn example_track() -> RaceTrack {
+        Day20::parse(EXAMPLE, ParseMode::Strict).unwrap()
+    }
+
+    #[test]
+    fn test_example_shortest_path() {
+        assert_eq!(example_track().shortest_path_length, 84);
+    }
+
+    #[test]
+    fn test_example_two_picosecond_cheats() {
+        let track = example_track();
+        // Cumulative counts from the puzzle's table of savings
+        assert_eq!(count_cheats(&track, 64), 1);
+        assert_eq!(count_cheats(&track, 40), 2);
+        assert_eq!(count_cheats(&track, 20), 5);
+        assert_eq!(count_cheats(&track, 2), 44);
+    }
+
+    #[test]
+    fn test_lenient_parse_skips_stray_characters() {
+        // The wall at (4, 1) separates two stretches of track six steps apart;
+        // a stray character in its place must not become a shortcut
+        let mut lines: Vec<String> = EXAMPLE.lines().map(str::to_string).collect();
+        lines[1].replace_range(4..5, "x");
+        let stray = lines.join("\n");
+        assert!(Day20::parse(&stray, ParseMode::Strict).is_err());
+
+        let track = Day20::parse(&stray, ParseMode::Lenient).unwrap();
+        let clean = example_track();
+        assert_eq!(track.shortest_path_length, clean.shortest_path_length);
+        for min_saving in [2, 20, 40, 64] {
+            assert_eq!(
+                count_cheats(&track, min_saving),
+                count_cheats(&clean, min_saving)
+            );
+        }
+    }
+}
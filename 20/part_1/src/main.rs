@@ -0,0 +1,37 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc_core::cli::{self, Args};
+use aoc_core::{Format, ParseMode, Result, Solution};
+use day20::{Day20, INPUT_PATH};
+
+fn run(mut args: Args) -> Result<()> {
+    let format: Format = args.parsed("--format")?.unwrap_or_default();
+    args.finish()?;
+
+    // Read the input from a file named "day_20.in"
+    let input = aoc_core::read_input(Some(Path::new(INPUT_PATH)))?;
+    if format == Format::Json {
+        return aoc_core::print_json::<Day20>(&input, ParseMode::Strict);
+    }
+
+    // Parse the grid and measure the track from both ends
+    let track = Day20::parse(&input, ParseMode::Strict)?;
+
+    println!(
+        "Shortest path length without cheating: {} picoseconds.",
+        track.shortest_path_length
+    );
+
+    let part_one = Day20::part1(&track)?;
+    println!(
+        "Number of cheats that save at least 100 picoseconds (Part 1): {}",
+        part_one
+    );
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    cli::report(run(Args::from_env()))
+}
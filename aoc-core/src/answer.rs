@@ -0,0 +1,67 @@
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i32, i64, u32, u64, usize);
+
+/// Values beyond `i128::MAX` are kept exactly as text.
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Number(n),
+            Err(_) => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_u128() {
+        assert_eq!(Answer::from(42u128), Answer::Number(42));
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::Text("340282366920938463463374607431768211455".to_string())
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use std::env;
+use std::process::ExitCode;
+
+use crate::{Error, Result};
+
+/// Command-line arguments of a day binary, consumed option by option.
+#[derive(Debug, Clone)]
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    /// Collects the arguments of the current process, without the program name.
+    pub fn from_env() -> Self {
+        Args::new(env::args().skip(1))
+    }
+
+    pub fn new(args: impl IntoIterator<Item = String>) -> Self {
+        Args {
+            args: args.into_iter().collect(),
+        }
+    }
+
+    /// Removes a boolean `name` flag, returning whether it was present.
+    pub fn flag(&mut self, name: &str) -> bool {
+        let before = self.args.len();
+        self.args.retain(|arg| arg != name);
+        self.args.len() != before
+    }
+
+    /// Removes `name <value>` and returns the value, or `None` if the option is absent.
+    pub fn value(&mut self, name: &str) -> Result<Option<String>> {
+        let Some(index) = self.args.iter().position(|arg| arg == name) else {
+            return Ok(None);
+        };
+        if index + 1 >= self.args.len() {
+            return Err(Error::Usage(format!("missing value for {}", name)));
+        }
+        let value = self.args.remove(index + 1);
+        self.args.remove(index);
+        Ok(Some(value))
+    }
+
+    /// Removes `name <value>` and parses the value, or returns `None` if the option is absent.
+    pub fn parsed<T>(&mut self, name: &str) -> Result<Option<T>>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.value(name)?
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| Error::Usage(format!("invalid value for {}: {}", name, e)))
+            })
+            .transpose()
+    }
+
+    /// Removes and returns the first positional argument. A lone '-' counts as positional.
+    pub fn positional(&mut self) -> Option<String> {
+        let index = self
+            .args
+            .iter()
+            .position(|arg| arg == "-" || !arg.starts_with('-'))?;
+        Some(self.args.remove(index))
+    }
+
+    /// Fails if any argument was left unconsumed.
+    pub fn finish(self) -> Result<()> {
+        match self.args.first() {
+            None => Ok(()),
+            Some(arg) => Err(Error::Usage(format!("unexpected argument '{}'", arg))),
+        }
+    }
+}
+
+/// Turns the outcome of a binary into its exit status, reporting any error on stderr:
+/// 2 for usage errors, 1 for everything else.
+pub fn report(result: Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e @ Error::Usage(_)) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(2)
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
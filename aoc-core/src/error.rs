@@ -0,0 +1,114 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Errors shared by every day: reading input, parsing it, and solving it.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Io {
+        /// The file being read, or `None` for stdin.
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// The input is malformed. Lines and columns are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input is well-formed but the puzzle has no answer for it.
+    NoAnswer(String),
+    /// The command line is invalid.
+    Usage(String),
+    /// A result does not match the one it was checked against.
+    Mismatch(String),
+}
+
+/// Result type used throughout the solutions.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn no_answer(message: impl Into<String>) -> Self {
+        Error::NoAnswer(message.into())
+    }
+
+    /// Names `path` as the file an I/O error happened on, unless it already
+    /// names one, for readers that do not know where their input comes from.
+    pub fn in_file(self, path: &Path) -> Self {
+        match self {
+            Error::Io { path: None, source } => Error::Io {
+                path: Some(path.to_path_buf()),
+                source,
+            },
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "failed to read '{}': {}", path.display(), source),
+            Error::Io { path: None, source } => write!(f, "failed to read stdin: {}", source),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            Error::NoAnswer(message) => write!(f, "{}", message),
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Mismatch(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io { path: None, source }
+    }
+}
+
+/// How a parser treats malformed lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Report malformed lines on stderr and skip them.
+    #[default]
+    Lenient,
+    /// Reject the whole input at the first malformed line.
+    Strict,
+}
+
+impl ParseMode {
+    /// Handles a malformed line: strict mode returns the error, lenient mode
+    /// reports it on stderr and returns `Ok` so the caller can skip the line.
+    pub fn skip_or_fail(self, error: Error) -> Result<()> {
+        match self {
+            ParseMode::Strict => Err(error),
+            ParseMode::Lenient => {
+                eprintln!("warning: skipping {}", error);
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+mod answer;
+pub mod cli;
+mod error;
+mod output;
+
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub use answer::Answer;
+pub use error::{Error, ParseMode, Result};
+pub use output::{json_string, print_json, Format};
+
+/// A puzzle solution split into a parse stage and one stage per part,
+/// so each stage can be called, tested and timed on its own.
+pub trait Solution {
+    /// Day of the calendar this solution belongs to.
+    const DAY: u8;
+
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+
+    fn parse(input: &str, mode: ParseMode) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    fn part2(input: &Self::Input) -> Result<Answer>;
+}
+
+/// Outcome of one part of a [`run`].
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Result<Answer>,
+    pub elapsed: Duration,
+}
+
+/// Outcome of parsing an input once and solving the requested parts.
+#[derive(Debug)]
+pub struct Run {
+    pub day: u8,
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/// Parses `input` and solves each part listed in `parts` (1 and/or 2), timing every stage.
+pub fn run<S: Solution>(input: &str, mode: ParseMode, parts: &[u8]) -> Result<Run> {
+    let start = Instant::now();
+    let parsed = S::parse(input, mode)?;
+    Ok(solve::<S>(&parsed, start.elapsed(), parts))
+}
+
+/// Solves each part listed in `parts` from an input parsed elsewhere, for days
+/// that parse in their own way. `parse` is reported as the parse time.
+pub fn solve<S: Solution>(parsed: &S::Input, parse: Duration, parts: &[u8]) -> Run {
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1(parsed),
+                2 => S::part2(parsed),
+                _ => Err(Error::no_answer(format!(
+                    "day {} has no part {}",
+                    S::DAY,
+                    part
+                ))),
+            };
+            PartRun {
+                part,
+                answer,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Run {
+        day: S::DAY,
+        parse,
+        parts,
+    }
+}
+
+/// Reads a whole puzzle input from `path`, or from stdin when `path` is `None`.
+pub fn read_input(path: Option<&Path>) -> Result<String> {
+    let result = match path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map(|_| text)
+        }
+    };
+    result.map_err(|source| Error::Io {
+        path: path.map(Path::to_path_buf),
+        source,
+    })
+}
+
+/// Opens `path`, or stdin when `path` is `None`, for reading input incrementally.
+pub fn open_input(path: Option<&Path>) -> Result<Box<dyn BufRead>> {
+    match path {
+        Some(path) => fs::File::open(path)
+            .map(|file| Box::new(io::BufReader::new(file)) as Box<dyn BufRead>)
+            .map_err(|source| Error::Io {
+                path: Some(path.to_path_buf()),
+                source,
+            }),
+        None => Ok(Box::new(io::stdin().lock())),
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::{Answer, Error, ParseMode, PartRun, Result, Run, Solution};
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// One human-readable line per part.
+    #[default]
+    Text,
+    /// One JSON record per line: `{"day", "part", "answer", "elapsed_ns"}`.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown format '{}' (expected 'text' or 'json')",
+                s
+            )),
+        }
+    }
+}
+
+/// Quotes and escapes `s` as a JSON string.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl Answer {
+    /// The answer as a JSON value: numbers stay numbers, everything else is a string.
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(s) => json_string(s),
+        }
+    }
+}
+
+impl PartRun {
+    /// The JSON record for this part. Failed parts get a null answer and an `error` field.
+    pub fn to_json(&self, day: u8) -> String {
+        let answer = match &self.answer {
+            Ok(answer) => answer.to_json(),
+            Err(e) => format!("null,\"error\":{}", json_string(&e.to_string())),
+        };
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ns\":{}}}",
+            day,
+            self.part,
+            answer,
+            self.elapsed.as_nanos()
+        )
+    }
+}
+
+impl Run {
+    /// Prints the parts in `format`. Text mode prints only the answered parts;
+    /// JSON mode prints a record for every part, including failed ones.
+    pub fn print(&self, format: Format) {
+        for part in &self.parts {
+            match (format, &part.answer) {
+                (Format::Json, _) => println!("{}", part.to_json(self.day)),
+                (Format::Text, Ok(answer)) => {
+                    println!("day {:02} part {}: {}", self.day, part.part, answer)
+                }
+                (Format::Text, Err(Error::NoAnswer(message))) => {
+                    println!("day {:02} part {}: {}", self.day, part.part, message)
+                }
+                (Format::Text, Err(_)) => {}
+            }
+        }
+    }
+
+    /// Returns the error of the first failed part, if any. A part with no
+    /// answer has not failed; its record already says why.
+    pub fn into_result(self) -> Result<()> {
+        let failed = self.parts.into_iter().find_map(|part| {
+            part.answer
+                .err()
+                .filter(|e| !matches!(e, Error::NoAnswer(_)))
+        });
+        match failed {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses `input`, solves both parts of `S` and prints them as JSON records.
+/// Fails with the parse error or the first part's error after printing.
+pub fn print_json<S: Solution>(input: &str, mode: ParseMode) -> Result<()> {
+    let run = crate::run::<S>(input, mode, &[1, 2])?;
+    run.print(Format::Json);
+    run.into_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_json_string_escapes() {
+        assert_eq!(json_string("56,27"), "\"56,27\"");
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn test_part_run_records() {
+        let answered = PartRun {
+            part: 1,
+            answer: Ok(Answer::from(432)),
+            elapsed: Duration::from_nanos(1500),
+        };
+        assert_eq!(
+            answered.to_json(18),
+            r#"{"day":18,"part":1,"answer":432,"elapsed_ns":1500}"#
+        );
+
+        let failed = PartRun {
+            part: 2,
+            answer: Err(Error::no_answer("no tree found")),
+            elapsed: Duration::from_nanos(7),
+        };
+        assert_eq!(
+            failed.to_json(14),
+            r#"{"day":14,"part":2,"answer":null,"error":"no tree found","elapsed_ns":7}"#
+        );
+
+        // A part with no answer does not fail the run, but any other error does
+        let run = |answer| Run {
+            day: 14,
+            parse: Duration::ZERO,
+            parts: vec![PartRun {
+                part: 2,
+                answer,
+                elapsed: Duration::ZERO,
+            }],
+        };
+        assert!(run(Err(Error::no_answer("no tree found")))
+            .into_result()
+            .is_ok());
+        assert!(run(Err(Error::parse(1, 1, "bad"))).into_result().is_err());
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert_eq!("text".parse::<Format>(), Ok(Format::Text));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Offsets of the four orthogonal neighbours: Up, Down, Left, Right.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+/// Offsets of all eight neighbours, orthogonal and diagonal.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A cell position on a grid.
+/// `x` is the column (distance from the left edge) and `y` is the row (distance from the top).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Point { x, y }
+    }
+
+    /// Shifts the point by `(dx, dy)`.
+    /// Returns `None` if either coordinate would become negative.
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+
+    /// Manhattan distance between two points.
+    pub fn manhattan(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Error returned when text cannot be turned into a rectangular grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGridError {
+    /// 1-based line number of the offending row.
+    pub line: usize,
+    /// Width established by the first row.
+    pub expected: usize,
+    /// Width of the offending row.
+    pub found: usize,
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected {} columns, found {}",
+            self.line, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl From<ParseGridError> for aoc_core::Error {
+    fn from(e: ParseGridError) -> Self {
+        // The row goes wrong one column past the shorter of the two widths
+        let column = e.expected.min(e.found) + 1;
+        let message = format!("expected {} columns, found {}", e.expected, e.found);
+        aoc_core::Error::parse(e.line, column, message)
+    }
+}
+
+/// A rectangular grid stored row by row in a single vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width` x `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses a grid from text, one row per line, converting each character with `cell`.
+    /// Blank trailing lines are ignored; every other row must have the same width.
+    pub fn parse_with<F>(input: &str, mut cell: F) -> Result<Self, ParseGridError>
+    where
+        F: FnMut(Point, char) -> T,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (y, line) in input.trim_end().lines().enumerate() {
+            let before = cells.len();
+            cells.extend(
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| cell(Point { x, y }, c)),
+            );
+            let found = cells.len() - before;
+
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(ParseGridError {
+                        line: y + 1,
+                        expected,
+                        found,
+                    });
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns true if the point lies inside the grid.
+    pub fn contains(&self, p: Point) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.cells[p.y * self.width + p.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.y * self.width + p.x])
+        } else {
+            None
+        }
+    }
+
+    /// Shifts `p` by `(dx, dy)`, returning the new point only if it is still on the grid.
+    pub fn step(&self, p: Point, dx: isize, dy: isize) -> Option<Point> {
+        p.offset(dx, dy).filter(|&q| self.contains(q))
+    }
+
+    /// Iterates over the in-bounds orthogonal neighbours of `p`.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS_4
+            .iter()
+            .filter_map(move |&(dx, dy)| self.step(p, dx, dy))
+    }
+
+    /// Iterates over the in-bounds orthogonal and diagonal neighbours of `p`.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRECTIONS_8
+            .iter()
+            .filter_map(move |&(dx, dy)| self.step(p, dx, dy))
+    }
+
+    /// Returns row `y` as a slice.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over the rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// Iterates over the cells of column `x` from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Iterates over every point of the grid in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point { x, y }))
+    }
+
+    /// Iterates over every cell together with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Returns the position of the first cell matching `pred`, in row-major order.
+    pub fn position<F>(&self, mut pred: F) -> Option<Point>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(p, _)| p)
+    }
+
+    /// Builds a grid of the same size by converting every cell with `f`.
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(Point, &T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(p, cell)| f(p, cell)).collect(),
+        }
+    }
+
+    /// Breadth-first search over orthogonal moves from `start`.
+    /// Only cells accepted by `passable` are entered; the start cell is always entered.
+    /// Returns the step distance to every reached cell, `None` for the rest.
+    pub fn bfs<F>(&self, start: Point, mut passable: F) -> Grid<Option<usize>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut distances = Grid::new(self.width, self.height, None);
+        if !self.contains(start) {
+            return distances;
+        }
+
+        let mut queue = VecDeque::new();
+        distances[start] = Some(0);
+        queue.push_back(start);
+
+        while let Some(current) = queue.pop_front() {
+            let next_distance = distances[current].map(|d| d + 1);
+            for next in self.neighbours4(current) {
+                if distances[next].is_none() && passable(&self[next]) {
+                    distances[next] = next_distance;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+impl Grid<char> {
+    /// Parses a character grid from text, one row per line.
+    pub fn parse(input: &str) -> Result<Self, ParseGridError> {
+        Self::parse_with(input, |_, c| c)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("point {} out of bounds", p))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("point {} out of bounds for {}x{} grid", p, width, height))
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = Grid::parse("ab\ncd\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[Point::new(1, 0)], 'b');
+        assert_eq!(grid[Point::new(0, 1)], 'c');
+        assert_eq!(grid.to_string(), "ab\ncd\n");
+    }
+
+    #[test]
+    fn test_parse_rejects_ragged_rows() {
+        let err = Grid::parse("abc\nde\nfgh").unwrap_err();
+        assert_eq!(
+            err,
+            ParseGridError {
+                line: 2,
+                expected: 3,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_neighbours_stay_in_bounds() {
+        let grid = Grid::new(3, 3, '.');
+        assert_eq!(grid.neighbours4(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point::new(2, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_row_and_column_views() {
+        let grid = Grid::parse("abc\ndef").unwrap();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let grid = Grid::parse("..#\n#..\n...").unwrap();
+        let distances = grid.bfs(Point::new(0, 0), |&c| c != '#');
+        assert_eq!(distances[Point::new(2, 2)], Some(4));
+        assert_eq!(distances[Point::new(2, 0)], None);
+        assert_eq!(distances[Point::new(0, 1)], None);
+    }
+}
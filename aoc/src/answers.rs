@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use aoc_core::{Error, Result};
+
+/// The registry shipped at the repository root.
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+/// Expected answers keyed by day and part, loaded from a small TOML subset:
+///
+/// ```toml
+/// [day18]
+/// part1 = 432
+/// part2 = "56,27"
+/// ```
+///
+/// Values are integers or double-quoted strings; `#` starts a comment.
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: BTreeMap<(u8, u8), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = aoc_core::read_input(Some(path))?;
+        Answers::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut answers = Answers::default();
+        let mut day = None;
+
+        for (line_num, raw) in text.lines().enumerate() {
+            let line_num = line_num + 1;
+            let line = strip_comment(raw).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[') {
+                let name = section
+                    .strip_suffix(']')
+                    .ok_or_else(|| Error::parse(line_num, raw.len() + 1, "expected ']'"))?;
+                let number = name.trim().strip_prefix("day").and_then(|n| n.parse().ok());
+                day = Some(number.ok_or_else(|| {
+                    Error::parse(
+                        line_num,
+                        column_of(raw, name),
+                        format!("expected a section like [day08], found [{}]", name),
+                    )
+                })?);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                Error::parse(line_num, column_of(raw, line), "expected 'key = value'")
+            })?;
+            let key = key.trim();
+            let part = match key {
+                "part1" => 1,
+                "part2" => 2,
+                _ => {
+                    return Err(Error::parse(
+                        line_num,
+                        column_of(raw, key),
+                        format!("unknown key '{}' (expected part1 or part2)", key),
+                    ))
+                }
+            };
+            let day = day.ok_or_else(|| {
+                Error::parse(line_num, 1, "answer given before any [dayNN] section")
+            })?;
+
+            let value = value.trim();
+            let answer = parse_value(value).ok_or_else(|| {
+                Error::parse(
+                    line_num,
+                    column_of(raw, value),
+                    format!("expected an integer or a quoted string, found '{}'", value),
+                )
+            })?;
+            if answers.entries.insert((day, part), answer).is_some() {
+                return Err(Error::parse(
+                    line_num,
+                    column_of(raw, key),
+                    format!("duplicate answer for day {} part {}", day, part),
+                ));
+            }
+        }
+
+        Ok(answers)
+    }
+
+    /// The recorded answer for one part, if any.
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.entries.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// Drops a trailing `#` comment, ignoring any `#` inside a quoted string.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(text) = value.strip_prefix('"') {
+        let text = text.strip_suffix('"')?;
+        return (!text.contains('"')).then(|| text.to_string());
+    }
+    value.parse::<i128>().ok().map(|n| n.to_string())
+}
+
+/// 1-based column of `part`, which must be a slice of `line`.
+fn column_of(line: &str, part: &str) -> usize {
+    part.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse(
+            "# expected answers\n\
+             [day08]\n\
+             part1 = 329\n\
+             part2 = \"1190\"  # strings work too\n\
+             \n\
+             [day18]\n\
+             part2 = \"56,27\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(8, 1), Some("329"));
+        assert_eq!(answers.get(8, 2), Some("1190"));
+        assert_eq!(answers.get(18, 1), None);
+        assert_eq!(answers.get(18, 2), Some("56,27"));
+    }
+
+    #[test]
+    fn test_parse_errors_have_positions() {
+        let err = Answers::parse("[day08]\npart3 = 1\n").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Parse {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+
+        let err = Answers::parse("[day08]\npart1 = abc\n").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Parse {
+                line: 2,
+                column: 9,
+                ..
+            }
+        ));
+
+        let err = Answers::parse("part1 = 1\n").unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 1, .. }));
+
+        let err = Answers::parse("[day08]\npart1 = 1\npart1 = 2\n").unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 3, .. }));
+    }
+}
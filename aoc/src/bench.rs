@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_core::{Error, ParseMode, Result};
+
+use crate::solvers::Solver;
+
+/// Summary of the timings of one stage over several runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarises a non-empty set of samples. The 95th percentile uses the
+    /// nearest-rank method, so with fewer than 20 runs it is the maximum.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+        Stats {
+            min: samples[0],
+            median,
+            p95,
+        }
+    }
+}
+
+/// The timings of one stage (`parse`, `part1` or `part2`) of one day.
+#[derive(Debug, Clone)]
+pub struct Stage {
+    pub day: u8,
+    pub name: String,
+    pub stats: Stats,
+}
+
+/// Parses and solves `text` with `solver` `runs` times and summarises each stage.
+/// Fails on the first parse or part error, since a failing stage has nothing to time,
+/// except that parts with no answer, like those not solved in Rust, are left out.
+pub fn bench(solver: &Solver, text: &str, runs: usize) -> Result<Vec<Stage>> {
+    let mut samples: BTreeMap<String, Vec<Duration>> = BTreeMap::new();
+    for _ in 0..runs {
+        let run = (solver.run)(text, ParseMode::Strict, &[1, 2])?;
+        samples
+            .entry("parse".to_string())
+            .or_default()
+            .push(run.parse);
+        for part in run.parts {
+            match part.answer {
+                Err(Error::NoAnswer(_)) => continue,
+                answer => answer?,
+            };
+            samples
+                .entry(format!("part{}", part.part))
+                .or_default()
+                .push(part.elapsed);
+        }
+    }
+
+    Ok(samples
+        .into_iter()
+        .map(|(name, samples)| Stage {
+            day: solver.day,
+            name,
+            stats: Stats::from_samples(samples),
+        })
+        .collect())
+}
+
+/// Stage timings saved from an earlier benchmark, keyed by day and stage name.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    stages: BTreeMap<(u8, String), Stats>,
+}
+
+impl Baseline {
+    /// Reads a baseline written by [`Baseline::save`]: one line per stage with the
+    /// day, the stage name and the min, median and p95 in nanoseconds.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = aoc_core::read_input(Some(path))?;
+        Baseline::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut baseline = Baseline::default();
+        for (line_num, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let parsed = match fields[..] {
+                [day, name, min, median, p95] => (|| {
+                    let nanos = |s: &str| s.parse().ok().map(Duration::from_nanos);
+                    let stats = Stats {
+                        min: nanos(min)?,
+                        median: nanos(median)?,
+                        p95: nanos(p95)?,
+                    };
+                    Some((day.parse().ok()?, name.to_string(), stats))
+                })(),
+                _ => None,
+            };
+            let (day, name, stats) = parsed.ok_or_else(|| {
+                Error::parse(
+                    line_num + 1,
+                    1,
+                    "expected '<day> <stage> <min_ns> <median_ns> <p95_ns>'",
+                )
+            })?;
+            baseline.stages.insert((day, name), stats);
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(stages: &[Stage], path: &Path) -> Result<()> {
+        let mut text = String::from("# day stage min_ns median_ns p95_ns\n");
+        for stage in stages {
+            let _ = writeln!(
+                text,
+                "{:02} {} {} {} {}",
+                stage.day,
+                stage.name,
+                stage.stats.min.as_nanos(),
+                stage.stats.median.as_nanos(),
+                stage.stats.p95.as_nanos()
+            );
+        }
+        fs::write(path, text).map_err(|source| Error::Io {
+            path: Some(path.to_path_buf()),
+            source,
+        })
+    }
+
+    pub fn get(&self, day: u8, name: &str) -> Option<&Stats> {
+        self.stages.get(&(day, name.to_string()))
+    }
+}
+
+/// Formats a duration with three significant digits in a readable unit.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos() as f64;
+    let (value, unit) = if nanos < 1e3 {
+        (nanos, "ns")
+    } else if nanos < 1e6 {
+        (nanos / 1e3, "µs")
+    } else if nanos < 1e9 {
+        (nanos / 1e6, "ms")
+    } else {
+        (nanos / 1e9, "s")
+    };
+    let decimals = match value {
+        v if v < 10.0 => 2,
+        v if v < 100.0 => 1,
+        _ => 0,
+    };
+    format!("{:.*}{}", decimals, value, unit)
+}
+
+/// One report line for `stage`, with the change in median against `baseline` if known.
+pub fn report_line(stage: &Stage, baseline: Option<&Stats>) -> String {
+    let mut line = format!(
+        "day {:02} {:<5}  min {:>8}  median {:>8}  p95 {:>8}",
+        stage.day,
+        stage.name,
+        format_duration(stage.stats.min),
+        format_duration(stage.stats.median),
+        format_duration(stage.stats.p95)
+    );
+    if let Some(baseline) = baseline {
+        let before = baseline.median.as_nanos() as f64;
+        let after = stage.stats.median.as_nanos() as f64;
+        let change = if before > 0.0 {
+            (after - before) / before * 100.0
+        } else {
+            0.0
+        };
+        let _ = write!(
+            line,
+            "  (baseline {}, {:+.1}%)",
+            format_duration(baseline.median),
+            change
+        );
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(ms(&[5, 1, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+
+        let stats = Stats::from_samples(ms(&[4, 1, 3, 2]));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+
+        // Nearest rank: the 95th of 1..=100 is 95, not the maximum.
+        let stats = Stats::from_samples(ms(&(1..=100).collect::<Vec<_>>()));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let stages = vec![Stage {
+            day: 9,
+            name: "part1".to_string(),
+            stats: Stats {
+                min: Duration::from_nanos(10),
+                median: Duration::from_nanos(20),
+                p95: Duration::from_nanos(30),
+            },
+        }];
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.txt", std::process::id()));
+        Baseline::save(&stages, &path).unwrap();
+        let baseline = Baseline::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(baseline.get(9, "part1"), Some(&stages[0].stats));
+        assert_eq!(baseline.get(9, "part2"), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512ns");
+        assert_eq!(format_duration(Duration::from_micros(1234)), "1.23ms");
+        assert_eq!(format_duration(Duration::from_millis(45678)), "45.7s");
+    }
+}
@@ -0,0 +1,348 @@
+mod answers;
+mod bench;
+mod solvers;
+
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc_core::{Error, Format, ParseMode};
+
+use answers::Answers;
+use bench::Baseline;
+use solvers::{Solver, SOLVERS};
+
+const USAGE: &str = "\
+Usage:
+    aoc run <DAY|all> [--part <1|2>] [--input <PATH|->] [--strict] [--format <text|json>]
+    aoc verify [DAY|all] [--answers <PATH>]
+    aoc bench <DAY|all> [--runs <N>] [--baseline <PATH>] [--save <PATH>]
+    aoc list
+
+Options:
+    --part <1|2>      Run only one part (default: both)
+    --input <PATH>    Read the puzzle input from PATH, or from stdin when PATH is '-'
+                      (default: the day's own .in file)
+    --strict          Reject malformed input lines instead of skipping them
+    --format <FMT>    Print answers as 'text' lines or as one 'json' record per part
+                      (default: text)
+    --answers <PATH>  Expected answers to verify against (default: answers.toml)
+    --runs <N>        Number of timed runs per day (default: 10)
+    --baseline <PATH> Compare median timings with a baseline saved by --save
+    --save <PATH>     Save the timings as a new baseline";
+
+/// Which registered days a command applies to.
+enum Days {
+    All,
+    One(u8),
+}
+
+enum Command {
+    Run {
+        days: Days,
+        part: Option<u8>,
+        input: Option<String>,
+        mode: ParseMode,
+        format: Format,
+    },
+    Verify {
+        days: Days,
+        answers: Option<String>,
+    },
+    Bench {
+        days: Days,
+        runs: usize,
+        baseline: Option<String>,
+        save: Option<String>,
+    },
+    List,
+}
+
+fn parse_days(day: Option<&str>) -> Result<Days, String> {
+    match day {
+        Some("all") => Ok(Days::All),
+        Some(day) => day
+            .parse()
+            .map(Days::One)
+            .map_err(|_| format!("invalid day '{}'", day)),
+        None => Err("missing day".to_string()),
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let command = args.next().ok_or("missing command")?;
+    match command.as_str() {
+        "run" => {
+            let days = parse_days(args.next().as_deref())?;
+
+            let mut part = None;
+            let mut input = None;
+            let mut mode = ParseMode::Lenient;
+            let mut format = Format::Text;
+            while let Some(flag) = args.next() {
+                let mut value = || {
+                    args.next()
+                        .ok_or_else(|| format!("missing value for {}", flag))
+                };
+                match flag.as_str() {
+                    "--part" => {
+                        part = Some(match value()?.as_str() {
+                            "1" => 1,
+                            "2" => 2,
+                            other => return Err(format!("invalid part '{}'", other)),
+                        })
+                    }
+                    "--input" => input = Some(value()?),
+                    "--strict" => mode = ParseMode::Strict,
+                    "--format" => format = value()?.parse()?,
+                    _ => return Err(format!("unknown option '{}'", flag)),
+                }
+            }
+
+            if matches!(days, Days::All) && input.is_some() {
+                return Err("--input cannot be combined with 'all'".to_string());
+            }
+            Ok(Command::Run {
+                days,
+                part,
+                input,
+                mode,
+                format,
+            })
+        }
+        "verify" => {
+            let mut days = Days::All;
+            let mut answers = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--answers" => {
+                        answers = Some(args.next().ok_or("missing value for --answers")?)
+                    }
+                    _ if arg.starts_with("--") => return Err(format!("unknown option '{}'", arg)),
+                    _ => days = parse_days(Some(&arg))?,
+                }
+            }
+            Ok(Command::Verify { days, answers })
+        }
+        "bench" => {
+            let days = parse_days(args.next().as_deref())?;
+            let mut runs = 10;
+            let mut baseline = None;
+            let mut save = None;
+            while let Some(flag) = args.next() {
+                let mut value = || {
+                    args.next()
+                        .ok_or_else(|| format!("missing value for {}", flag))
+                };
+                match flag.as_str() {
+                    "--runs" => {
+                        let value = value()?;
+                        runs = match value.parse() {
+                            Ok(runs) if runs > 0 => runs,
+                            _ => return Err(format!("invalid number of runs '{}'", value)),
+                        }
+                    }
+                    "--baseline" => baseline = Some(value()?),
+                    "--save" => save = Some(value()?),
+                    _ => return Err(format!("unknown option '{}'", flag)),
+                }
+            }
+            Ok(Command::Bench {
+                days,
+                runs,
+                baseline,
+                save,
+            })
+        }
+        "list" => Ok(Command::List),
+        _ => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+/// Reads the puzzle input from a path, from stdin for '-', or from the day's default file.
+fn read_input(solver: &Solver, input: Option<&str>) -> aoc_core::Result<String> {
+    match input {
+        Some("-") => aoc_core::read_input(None),
+        Some(path) => aoc_core::read_input(Some(Path::new(path))),
+        None => aoc_core::read_input(Some(Path::new(solver.input_path))),
+    }
+}
+
+/// Runs the selected parts of one day, printing one line or record per part.
+fn run_day(
+    solver: &Solver,
+    part: Option<u8>,
+    input: Option<&str>,
+    mode: ParseMode,
+    format: Format,
+) -> Result<(), String> {
+    let text = read_input(solver, input).map_err(|e| e.to_string())?;
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let run =
+        (solver.run)(&text, mode, &parts).map_err(|e| format!("day {:02}: {}", solver.day, e))?;
+    run.print(format);
+    // Parts with no answer have printed why and, as in the day binaries, are not failures
+    let failed = run.parts.into_iter().find(|part| {
+        part.answer
+            .as_ref()
+            .is_err_and(|e| !matches!(e, Error::NoAnswer(_)))
+    });
+    match failed {
+        Some(part) => Err(format!(
+            "day {:02} part {}: {}",
+            run.day,
+            part.part,
+            part.answer.unwrap_err()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Solves both parts of one day from its own input and compares them with the
+/// recorded answers, printing one line per part. Returns how many parts failed.
+fn verify_day(solver: &Solver, answers: &Answers) -> usize {
+    let mut failed = 0;
+    let mut fail = |part: u8, message: String| {
+        println!("day {:02} part {}: FAIL {}", solver.day, part, message);
+        failed += 1;
+    };
+
+    let run = aoc_core::read_input(Some(Path::new(solver.input_path)))
+        .and_then(|text| (solver.run)(&text, ParseMode::Strict, &[1, 2]));
+    let run = match run {
+        Ok(run) => run,
+        Err(e) => {
+            for part in [1, 2] {
+                fail(part, e.to_string());
+            }
+            return failed;
+        }
+    };
+
+    for part in run.parts {
+        let expected = answers.get(run.day, part.part);
+        match (part.answer, expected) {
+            (Err(Error::NoAnswer(message)), None) => {
+                println!("day {:02} part {}: SKIP {}", run.day, part.part, message)
+            }
+            (Err(e), _) => fail(part.part, e.to_string()),
+            (Ok(answer), None) => println!(
+                "day {:02} part {}: SKIP no recorded answer (got {})",
+                run.day, part.part, answer
+            ),
+            (Ok(answer), Some(expected)) if answer.to_string() == expected => {
+                println!("day {:02} part {}: ok {}", run.day, part.part, answer)
+            }
+            (Ok(answer), Some(expected)) => {
+                fail(part.part, format!("expected {}, got {}", expected, answer))
+            }
+        }
+    }
+    failed
+}
+
+/// The registered solvers a command applies to.
+fn selected(days: Days) -> Result<&'static [Solver], String> {
+    match days {
+        Days::All => Ok(SOLVERS),
+        Days::One(day) => solvers::find(day)
+            .map(std::slice::from_ref)
+            .ok_or_else(|| format!("day {} has no Rust solver", day)),
+    }
+}
+
+fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Run {
+            days,
+            part,
+            input,
+            mode,
+            format,
+        } => match days {
+            Days::All => SOLVERS
+                .iter()
+                .try_for_each(|solver| run_day(solver, part, None, mode, format)),
+            Days::One(day) => {
+                let solver =
+                    solvers::find(day).ok_or_else(|| format!("day {} has no Rust solver", day))?;
+                run_day(solver, part, input.as_deref(), mode, format)
+            }
+        },
+        Command::Verify { days, answers } => {
+            let path = answers.as_deref().unwrap_or(answers::DEFAULT_PATH);
+            let answers = Answers::load(Path::new(path)).map_err(|e| match e {
+                Error::Parse { .. } => format!("{}: {}", path, e),
+                e => e.to_string(),
+            })?;
+            let failed: usize = selected(days)?
+                .iter()
+                .map(|solver| verify_day(solver, &answers))
+                .sum();
+            match failed {
+                0 => Ok(()),
+                1 => Err("1 part failed verification".to_string()),
+                n => Err(format!("{} parts failed verification", n)),
+            }
+        }
+        Command::Bench {
+            days,
+            runs,
+            baseline,
+            save,
+        } => {
+            let baseline = baseline
+                .map(|path| {
+                    Baseline::load(Path::new(&path)).map_err(|e| format!("{}: {}", path, e))
+                })
+                .transpose()?;
+
+            let mut stages = Vec::new();
+            for solver in selected(days)? {
+                let text = read_input(solver, None).map_err(|e| e.to_string())?;
+                let day_stages = bench::bench(solver, &text, runs)
+                    .map_err(|e| format!("day {:02}: {}", solver.day, e))?;
+                for stage in &day_stages {
+                    let before = baseline
+                        .as_ref()
+                        .and_then(|baseline| baseline.get(stage.day, &stage.name));
+                    println!("{}", bench::report_line(stage, before));
+                }
+                stages.extend(day_stages);
+            }
+
+            match save {
+                Some(path) => Baseline::save(&stages, Path::new(&path)).map_err(|e| e.to_string()),
+                None => Ok(()),
+            }
+        }
+        Command::List => {
+            for solver in SOLVERS {
+                println!("{:02}", solver.day);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
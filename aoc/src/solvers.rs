@@ -0,0 +1,36 @@
+use aoc_core::{ParseMode, Result, Run, Solution};
+
+/// Parses an input and solves the listed parts of one day.
+pub type RunFn = fn(&str, ParseMode, &[u8]) -> Result<Run>;
+
+/// A Rust day registered with the runner.
+pub struct Solver {
+    pub day: u8,
+    /// Input file used when no `--input` is given.
+    pub input_path: &'static str,
+    pub run: RunFn,
+}
+
+impl Solver {
+    const fn new<S: Solution>(input_path: &'static str) -> Self {
+        Solver {
+            day: S::DAY,
+            input_path,
+            run: aoc_core::run::<S>,
+        }
+    }
+}
+
+/// Every Rust day, in day order.
+pub const SOLVERS: &[Solver] = &[
+    Solver::new::<day08::Day08>(day08::INPUT_PATH),
+    Solver::new::<day09::Day09>(day09::INPUT_PATH),
+    Solver::new::<day14::Day14>(day14::INPUT_PATH),
+    Solver::new::<day18::Day18>(day18::INPUT_PATH),
+    Solver::new::<day20::Day20>(day20::INPUT_PATH),
+];
+
+/// Looks up the solver registered for `day`.
+pub fn find(day: u8) -> Option<&'static Solver> {
+    SOLVERS.iter().find(|solver| solver.day == day)
+}